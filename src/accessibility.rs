@@ -0,0 +1,171 @@
+///
+/// Descriptions of the DatePicker for assistive technologies
+///
+use chrono::prelude::*;
+
+/// What an element of the [`DatePicker`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+
+    /// the whole [`DatePicker`]
+    Group,
+    TextInput,
+
+    /// the calendar under the input
+    Dialog,
+
+    /// a month
+    Grid,
+    Row,
+
+    /// the name of a weekday
+    ColumnHeader,
+
+    /// a day
+    GridCell,
+    Button,
+}
+
+/// An element of the [`DatePicker`], with the elements it contains,
+/// as a screen reader bridge would expose it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub role: Role,
+
+    /// what is spoken for the element
+    pub label: String,
+
+    /// what is spoken after the label, e.g. the name of a holiday
+    pub description: Option<String>,
+
+    /// the element where the keyboard would act: the selected day
+    pub is_focused: bool,
+    pub is_selected: bool,
+    pub children: Vec<Node>,
+}
+
+impl Node {
+
+    /// create a [`Node`] without children
+    pub fn new(role: Role, label: &str) -> Self {
+        Self {
+            role,
+            label: String::from(label),
+            description: None,
+            is_focused: false,
+            is_selected: false,
+            children: Vec::new(),
+        }
+    }
+
+    /// add what is spoken after the label
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(String::from(description));
+        self
+    }
+
+    /// add the children of the [`Node`]
+    pub fn children(mut self, children: Vec<Node>) -> Self {
+        self.children = children;
+        self
+    }
+
+    /// the focused node, itself or among its descendants
+    pub fn focused(&self) -> Option<&Node> {
+        self.find(&|node| node.is_focused)
+    }
+
+    /// the first node with the role and label, itself or among its descendants
+    pub fn find_by_label(&self, role: Role, label: &str) -> Option<&Node> {
+        self.find(&|node| node.role == role && node.label == label)
+    }
+
+    /// the first node matching the predicate, depth first
+    pub fn find(&self, predicate: &dyn Fn(&Node) -> bool) -> Option<&Node> {
+        if predicate(self) {
+            return Some(self)
+        }
+
+        self.children.iter().find_map(|child| child.find(predicate))
+    }
+}
+
+/// the label of a day, e.g. "Friday, 5 June 2020, selected, holiday",
+/// the name of the holiday being its description
+pub fn day_label(
+    date: NaiveDate,
+    is_selected: bool,
+    is_today: bool,
+    is_holiday: bool,
+    events: usize,
+) -> String {
+    let mut label = date.format("%A, %-d %B %Y").to_string();
+
+    if is_selected {
+        label.push_str(", selected");
+    }
+    if is_today {
+        label.push_str(", today");
+    }
+    if is_holiday {
+        label.push_str(", holiday");
+    }
+    match events {
+        0 => {}
+        1 => label.push_str(", 1 event"),
+        n => label.push_str(&format!(", {} events", n)),
+    }
+
+    label
+}
+
+/// the label of consecutive months, e.g. "June 2020" or "June 2020 to August 2020"
+pub fn months_label(first: (i32, u32), last: (i32, u32)) -> String {
+    let name = |(year, month): (i32, u32)| {
+        NaiveDate::from_ymd(year, month, 1).format("%B %Y").to_string()
+    };
+
+    if first == last {
+        name(first)
+    } else {
+        format!("{} to {}", name(first), name(last))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_labels() {
+        let date = NaiveDate::from_ymd(2020, 6, 5);
+
+        assert_eq!(day_label(date, false, false, false, 0), "Friday, 5 June 2020");
+        assert_eq!(
+            day_label(date, true, true, true, 2),
+            "Friday, 5 June 2020, selected, today, holiday, 2 events",
+        );
+        assert_eq!(day_label(date, false, false, false, 1), "Friday, 5 June 2020, 1 event");
+    }
+
+    #[test]
+    fn months_labels() {
+        assert_eq!(months_label((2020, 6), (2020, 6)), "June 2020");
+        assert_eq!(months_label((2020, 12), (2021, 1)), "December 2020 to January 2021");
+    }
+
+    #[test]
+    fn find_nodes() {
+        let tree = Node::new(Role::Group, "").children(vec![
+            Node::new(Role::TextInput, "2020-06-05"),
+            Node::new(Role::Grid, "June 2020").children(vec![Node {
+                is_focused: true,
+                ..Node::new(Role::GridCell, "Friday, 5 June 2020")
+            }]),
+        ]);
+
+        assert_eq!(tree.focused().map(|node| node.label.as_str()), Some("Friday, 5 June 2020"));
+        assert!(tree.find_by_label(Role::Grid, "June 2020").is_some());
+        assert!(tree.find_by_label(Role::GridCell, "June 2020").is_none());
+    }
+}
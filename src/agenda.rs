@@ -0,0 +1,496 @@
+///
+/// Agenda Widget: the events of the days around the date
+/// selected in a DatePicker, as a scrolled list
+///
+use iced_graphics::{Backend, Defaults, Primitive, Renderer};
+use iced_native::{
+    layout, mouse, Background, Color, Element, Hasher, Layout, Length,
+    Point, Size, Widget, Event, Clipboard, Rectangle, Font, HorizontalAlignment,
+    VerticalAlignment, Vector,
+};
+
+use chrono::prelude::*;
+use chrono::Duration;
+
+use crate::date_picker;
+use crate::dates::{last_day_of_month, week_start};
+use crate::events::{CalendarEvent, EventSource};
+use crate::style::{BACKGROUND_COLOR, BORDER_COLOR, FIRST_TEXT_COLOR, HIGH_LIGHT_COLOR};
+
+// darker than the picker's second text color, for the days without events
+const MUTED_TEXT_COLOR: Color = Color{r: 150.0/255.0, g: 150.0/255.0, b: 150.0/255.0, a: 1.0};
+
+/// Which days around the selected date an [`Agenda`] lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Span {
+    Day,
+
+    /// from the Monday of the selected date
+    Week,
+    Month,
+}
+
+/// A line of an [`Agenda`]
+enum Line<'a> {
+
+    /// the heading of a day
+    Day(NaiveDate),
+    Event(NaiveDate, &'a CalendarEvent),
+
+    /// a day without events
+    Empty,
+}
+
+/// The events of the days around the date selected in a [`DatePicker`]
+pub struct Agenda<'a> {
+    state: &'a mut State,
+
+    /// the selected date, none when the picker was cleared
+    date: Option<NaiveDate>,
+    events: &'a dyn EventSource,
+    span: Span,
+    w: u32,
+    h: u32,
+    size: Option<u16>,
+}
+
+impl<'a> Agenda<'a> {
+
+    /// create a new [`Agenda`] of the date selected in the [`DatePicker`]
+    /// with the given state, listing the events of the source,
+    /// empty when no date is selected
+    pub fn new(
+        w: u32,
+        h: u32,
+        state: &'a mut State,
+        picker: &date_picker::State,
+        events: &'a dyn EventSource,
+    ) -> Self {
+        Self {
+            state,
+            date: picker.value(),
+            events,
+            span: Span::Week,
+            w,
+            h,
+            size: None,
+        }
+    }
+
+    /// Sets which days are listed, the week of the selected date by default
+    pub fn span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// Sets the text size of the [`Agenda`].
+    pub fn size(mut self, size: u16) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// the height of a line
+    fn line_height(&self) -> f32 {
+        self.size.unwrap_or(20) as f32 * 1.5
+    }
+
+    /// the listed days, none without a selected date
+    fn days(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let date = self.date?;
+
+        Some(match self.span {
+            Span::Day => (date, date),
+            Span::Week => {
+                let first = week_start(date);
+                (first, first + Duration::days(6))
+            }
+            Span::Month => (
+                date.with_day(1).unwrap_or(date),
+                last_day_of_month(date.year(), date.month()).unwrap_or(date),
+            ),
+        })
+    }
+
+    /// the lines of the listed days: each heading, then its events,
+    /// the all-day ones first
+    fn lines(&self) -> Vec<Line<'a>> {
+        let mut lines = Vec::new();
+        let (first, last) = match self.days() {
+            Some(days) => days,
+            None => return lines,
+        };
+
+        let mut date = first;
+        while date <= last {
+            lines.push(Line::Day(date));
+
+            let mut events = self.events.events_on(date);
+            events.sort_by_key(|event| (!event.all_day, event.start.time()));
+
+            if events.is_empty() {
+                lines.push(Line::Empty);
+            }
+            lines.extend(events.into_iter().map(|event| Line::Event(date, event)));
+
+            date = date.succ();
+        }
+
+        lines
+    }
+
+    /// how far the list is scrolled: where the user left it,
+    /// or at the selected day when it changed
+    fn scroll(&self, lines: &[Line<'_>]) -> f32 {
+        let height = self.line_height();
+        let max = (lines.len() as f32 * height - self.h as f32).max(0.0);
+
+        let scroll = if self.state.date == self.date {
+            self.state.scroll
+        } else {
+            lines
+                .iter()
+                .position(|line| matches!(line, Line::Day(date) if Some(*date) == self.date))
+                .map_or(0.0, |i| i as f32 * height)
+        };
+
+        scroll.max(0.0).min(max)
+    }
+}
+
+/// the times of the event on the date, e.g. "09:00 – 10:30", "All day"
+fn times(date: NaiveDate, event: &CalendarEvent) -> String {
+    if event.all_day {
+        return String::from("All day")
+    }
+
+    // a repetition starts on the date, a long event may go on from before
+    let length = event.end - event.start;
+    let start = if event.recurrence.is_some() {
+        date.and_time(event.start.time())
+    } else {
+        event.start
+    };
+    let end = start + length;
+
+    format!(
+        "{} – {}",
+        if start.date() == date { start.format("%H:%M").to_string() } else { String::from("…") },
+        if end.date() == date { end.format("%H:%M").to_string() } else { String::from("…") },
+    )
+}
+
+impl<'a, Message, B> Widget<Message, Renderer<B>> for Agenda<'a>
+where
+    B: Backend,
+{
+    fn width(&self) -> Length {
+        Length::from(self.w as u16)
+    }
+
+    fn height(&self) -> Length {
+        Length::from(self.h as u16)
+    }
+
+    fn layout(
+        &self,
+        _renderer: &Renderer<B>,
+        _limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(Size::new(self.w as f32, self.h as f32))
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        use std::hash::Hash;
+        struct Marker;
+        std::any::TypeId::of::<Marker>().hash(state);
+
+        self.w.hash(state);
+        self.h.hash(state);
+    }
+
+    /// Draw the visible lines, under a frame
+    fn draw(
+        &self,
+        _renderer: &mut Renderer<B>,
+        _defaults: &Defaults,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+    ) -> (Primitive, mouse::Interaction) {
+        let bounds = layout.bounds();
+        let height = self.line_height();
+        let size = self.size.unwrap_or(20) as f32;
+        let lines = self.lines();
+        let scroll = self.scroll(&lines);
+
+        let mut v = Vec::new();
+        let first = (scroll / height).floor() as usize;
+        let last = ((scroll + bounds.height) / height).ceil() as usize;
+
+        for (i, line) in lines.iter().enumerate().take(last).skip(first) {
+            let b = Rectangle {
+                x: bounds.x,
+                y: bounds.y + i as f32 * height - scroll,
+                width: bounds.width,
+                height,
+            };
+
+            let (content, color, background, indent) = match line {
+                Line::Day(date) => (
+                    date.format("%A, %-d %B").to_string(),
+                    FIRST_TEXT_COLOR,
+                    if Some(*date) == self.date { HIGH_LIGHT_COLOR } else { BACKGROUND_COLOR },
+                    0.5,
+                ),
+                Line::Event(date, event) => (
+                    format!("{}  {}", times(*date, event), event.summary),
+                    FIRST_TEXT_COLOR,
+                    Color::WHITE,
+                    1.0,
+                ),
+                Line::Empty => (
+                    String::from("No events"),
+                    MUTED_TEXT_COLOR,
+                    Color::WHITE,
+                    1.0,
+                ),
+            };
+
+            v.push(Primitive::Quad {
+                bounds: b,
+                background: Background::Color(background),
+                border_radius: 0,
+                border_width: 0,
+                border_color: Color::TRANSPARENT,
+            });
+
+            v.push(Primitive::Text {
+                content,
+                bounds: Rectangle {
+                    x: b.x + indent * size,
+                    y: b.center_y(),
+                    ..b
+                },
+                color,
+                size,
+                font: Font::default(),
+                horizontal_alignment: HorizontalAlignment::Left,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+        }
+
+        // no date, e.g. the picker was cleared
+        if lines.is_empty() {
+            v.push(Primitive::Text {
+                content: String::from("No date selected"),
+                bounds: Rectangle {
+                    x: bounds.x + 0.5 * size,
+                    y: bounds.y + height / 2.0,
+                    width: bounds.width,
+                    height,
+                },
+                color: MUTED_TEXT_COLOR,
+                size,
+                font: Font::default(),
+                horizontal_alignment: HorizontalAlignment::Left,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+        }
+
+        (
+            Primitive::Group {
+                primitives: vec![
+                    Primitive::Clip {
+                        bounds,
+                        offset: Vector::new(0, 0),
+                        content: Box::new(Primitive::Group {
+                            primitives: v
+                        }),
+                    },
+                    Primitive::Quad {
+                        bounds,
+                        background: Background::Color(Color::TRANSPARENT),
+                        border_radius: 0,
+                        border_width: 1,
+                        border_color: BORDER_COLOR,
+                    },
+                ]
+            },
+            mouse::Interaction::default(),
+        )
+    }
+
+    // scroll the list with the mouse wheel
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _messages: &mut Vec<Message>,
+        _renderer: &Renderer<B>,
+        _clipboard: Option<&dyn Clipboard>,
+    ) {
+        if let Event::Mouse(mouse::Event::WheelScrolled { delta }) = event {
+            if !layout.bounds().contains(cursor_position) {
+                return
+            }
+
+            let pixels = match delta {
+                mouse::ScrollDelta::Lines { y, .. } => -y * self.line_height(),
+                mouse::ScrollDelta::Pixels { y, .. } => -y,
+            };
+
+            let scroll = self.scroll(&self.lines());
+            self.state.scroll = scroll + pixels;
+            self.state.date = self.date;
+        }
+    }
+}
+
+impl<'a, Message, B> Into<Element<'a, Message, Renderer<B>>> for Agenda<'a>
+where
+    B: Backend,
+{
+    fn into(self) -> Element<'a, Message, Renderer<B>> {
+        Element::new(self)
+    }
+}
+
+/// The state of an [`Agenda`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct State {
+
+    /// how far the list is scrolled, in pixels
+    scroll: f32,
+
+    /// the selected date when the list was scrolled,
+    /// it scrolls back to the new one when it changes
+    date: Option<NaiveDate>,
+}
+
+impl State {
+
+    /// Creates a new [`State`], scrolled at the selected day
+    pub fn new() -> State {
+        State::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date_picker::DatePicker;
+    use crate::headless::{self, Headless};
+    use crate::snapshot::serialize;
+    use iced_native::{Cache, UserInterface};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd(2020, 6, day)
+    }
+
+    /// a picker on the date, or cleared
+    fn picker(date: Option<NaiveDate>) -> date_picker::State {
+        let mut picker = date_picker::State::with_date(NaiveDate::from_ymd(2020, 6, 5));
+        let _ = DatePicker::new(350, &mut picker, "", "", |_| ()).date(date);
+        picker
+    }
+
+    /// two events on Wednesday 3 June, the all-day one last, and one on Friday
+    fn events() -> Vec<CalendarEvent> {
+        vec![
+            CalendarEvent::timed("Standup", date(3).and_hms(9, 0, 0), date(3).and_hms(9, 15, 0)),
+            CalendarEvent::all_day("Conference", date(3), date(3)),
+            CalendarEvent::timed("Review", date(5).and_hms(14, 0, 0), date(5).and_hms(15, 0, 0)),
+        ]
+    }
+
+    /// the lines as text, e.g. "3 June", "Standup" or "-"
+    fn texts(lines: &[Line<'_>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| match line {
+                Line::Day(date) => date.format("%-d %B").to_string(),
+                Line::Event(_, event) => event.summary.clone(),
+                Line::Empty => String::from("-"),
+            })
+            .collect()
+    }
+
+    /// how far an agenda 60 pixels high, of lines 30 pixels high, is scrolled
+    fn scroll(state: &mut State, picker: &date_picker::State, events: &dyn EventSource) -> f32 {
+        let agenda = Agenda::new(320, 60, state, picker, events);
+        agenda.scroll(&agenda.lines())
+    }
+
+    #[test]
+    fn lists_the_week_of_the_selected_date() {
+        let (mut state, picker, events) = (State::new(), picker(Some(date(5))), events());
+        let agenda = Agenda::new(320, 240, &mut state, &picker, &events);
+
+        assert_eq!(
+            texts(&agenda.lines()),
+            [
+                "1 June", "-", "2 June", "-", "3 June", "Conference", "Standup",
+                "4 June", "-", "5 June", "Review", "6 June", "-", "7 June", "-",
+            ],
+        );
+    }
+
+    #[test]
+    fn lists_the_day_or_the_month() {
+        let (mut state, picker, events) = (State::new(), picker(Some(date(3))), events());
+
+        let day = Agenda::new(320, 240, &mut state, &picker, &events).span(Span::Day);
+        assert_eq!(texts(&day.lines()), ["3 June", "Conference", "Standup"]);
+
+        let month = Agenda::new(320, 240, &mut state, &picker, &events).span(Span::Month);
+        assert_eq!(month.lines().iter().filter(|line| matches!(line, Line::Day(_))).count(), 30);
+    }
+
+    #[test]
+    fn scrolls_to_the_selected_day() {
+        let events = events();
+        let mut state = State::new();
+
+        // 5 June is the 10th line
+        assert_eq!(scroll(&mut state, &picker(Some(date(5))), &events), 9.0 * 30.0);
+
+        // the last lines stay at the bottom
+        assert_eq!(scroll(&mut state, &picker(Some(date(7))), &events), 15.0 * 30.0 - 60.0);
+    }
+
+    #[test]
+    fn wheel_scrolls_until_the_date_changes() {
+        let events = events();
+        let mut state = State::new();
+        let friday = picker(Some(date(5)));
+
+        {
+            let mut renderer = Renderer::new(Headless);
+            let agenda = Agenda::new(320, 60, &mut state, &friday, &events);
+            let mut user_interface = UserInterface::build(agenda, Size::new(320.0, 60.0), Cache::new(), &mut renderer);
+            let wheel = Event::Mouse(mouse::Event::WheelScrolled { delta: mouse::ScrollDelta::Lines { x: 0.0, y: 1.0 } });
+
+            let _: Vec<()> = user_interface.update(vec![wheel], Point::new(10.0, 10.0), None, &renderer);
+        }
+
+        // a line up, and kept while the date stays
+        assert_eq!(scroll(&mut state, &friday, &events), 8.0 * 30.0);
+        assert_eq!(scroll(&mut state, &friday, &events), 8.0 * 30.0);
+
+        // back to the new selected day
+        assert_eq!(scroll(&mut state, &picker(Some(date(3))), &events), 4.0 * 30.0);
+    }
+
+    #[test]
+    fn cleared_picker_lists_nothing() {
+        let (mut state, picker, events) = (State::new(), picker(None), events());
+
+        let agenda = Agenda::new(320, 240, &mut state, &picker, &events);
+        assert!(agenda.lines().is_empty());
+
+        let (primitive, _) = headless::draw::<(), _>(agenda, Size::new(320.0, 240.0));
+        let drawn = serialize(&primitive);
+        assert!(drawn.contains("\"No date selected\""));
+        assert!(!drawn.contains("June"));
+    }
+}
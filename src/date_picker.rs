@@ -0,0 +1,3072 @@
+///
+/// Custom DatePicker Widget
+///
+use iced_graphics::{Backend, Defaults, Primitive, Renderer};
+use iced_native::{
+    keyboard, layout, mouse, Background, Color, Element, Hasher, Layout, Length,
+    Point, Size, Widget, Event, Clipboard, Rectangle, Font, HorizontalAlignment,
+    VerticalAlignment, Vector
+};
+
+use chrono::prelude::*;
+use std::time::{Duration, Instant};
+
+use crate::accessibility::{self, Node, Role};
+use crate::events::EventSource;
+use crate::holiday::{Holiday, HolidayProvider};
+use crate::dates::{add_months, days_in_month, week_start, WEEKDAYS};
+use crate::parsing::{self, DateParser};
+use crate::presets::Preset;
+use crate::recurrence::{Frequency, Recurrence, RecurrenceRule};
+use crate::style::{BACKGROUND_COLOR, BORDER_COLOR, FIRST_TEXT_COLOR, HIGH_LIGHT_COLOR, SECOND_TEXT_COLOR};
+
+const WEEK_DAYS: [&str; 7] = ["Mon","Tue","Web","Thu","Fri","Sat","Sun"];
+const FREQUENCIES: [Frequency; 4] = [Frequency::Daily, Frequency::Weekly, Frequency::Monthly, Frequency::Yearly];
+const FREQUENCY_LABELS: [&str; 4] = ["Day", "Week", "Month", "Year"];
+const MAX_INTERVAL: u32 = 99;
+const ANIMATION_DURATION: Duration = Duration::from_millis(250);
+const SCROLL_UNIT: f32 = 1000.0;
+
+// the text sizes relative to the side of a cell, unless they are set
+const HEADER_TEXT_RATIO: f32 = 24.0/(400.0/7.0);
+const DAY_TEXT_RATIO: f32 = 36.0/(400.0/7.0);
+
+const HOLIDAY_COLOR: Color = Color{r: 250.0/255.0, g: 222.0/255.0, b: 222.0/255.0, a: 1.0};
+const HOLIDAY_TEXT_COLOR: Color = Color{r: 190.0/255.0, g: 40.0/255.0, b: 40.0/255.0, a: 1.0};
+const RECURRENCE_COLOR: Color = Color{r: 1.0, g: 226.0/255.0, b: 160.0/255.0, a: 1.0};
+const MARKER_COLOR: Color = Color{r: 60.0/255.0, g: 120.0/255.0, b: 200.0/255.0, a: 1.0};
+
+/// How the months of a [`DatePicker`] are placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Arrangement {
+    Row,
+    Column,
+}
+
+/// How the [`DatePicker`] moves between months, opens and closes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Animation {
+
+    /// no animation: every change shows at once
+    None,
+
+    /// the months slide sideways, the calendar rolls down
+    Slide,
+
+    /// the months and the calendar fade in and out
+    Fade,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Animation::None
+    }
+}
+
+/// A part of the [`DatePicker`] which can be clicked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Input,
+
+    /// a day of the month of the selected date
+    Day(u32),
+
+    /// a shown date, in the grid of its month
+    Date(NaiveDate),
+    Previous,
+    Next,
+
+    /// a preset of the side panel
+    Preset(Preset),
+
+    /// the × of the input, of a nullable date
+    ClearInput,
+
+    /// Clear in the footer, of a nullable date
+    Clear,
+
+    /// anywhere out of the [`DatePicker`]
+    Outside,
+}
+
+/// A day cell of a month grid, as given to a [`CellRenderer`]
+#[derive(Debug, Clone, Copy)]
+pub struct Cell {
+    pub date: NaiveDate,
+    pub bounds: Rectangle,
+    pub is_selected: bool,
+    pub is_hovered: bool,
+
+    /// the day can't be picked
+    pub is_disabled: bool,
+
+    /// the day belongs to the month before or after the grid's one
+    pub is_other_month: bool,
+    pub is_today: bool,
+    pub is_holiday: bool,
+
+    /// a recurrence occurs on the day
+    pub is_occurrence: bool,
+    pub has_events: bool,
+
+    /// the font and size of the day number: the bold font
+    /// for today and the selected day
+    pub font: Font,
+    pub text_size: f32,
+}
+
+/// Draws the day cells of a [`DatePicker`].
+/// Closures taking a [`Cell`] are renderers too
+pub trait CellRenderer {
+
+    /// the primitives of the cell, within its bounds
+    fn draw(&self, cell: &Cell) -> Vec<Primitive>;
+}
+
+impl<F> CellRenderer for F
+where
+    F: Fn(&Cell) -> Vec<Primitive>,
+{
+    fn draw(&self, cell: &Cell) -> Vec<Primitive> {
+        self(cell)
+    }
+}
+
+/// The default look of the day cells: the number of the day in a box,
+/// colored for the selection, recurrences and holidays,
+/// with a marker under the days with events
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultCell;
+
+impl CellRenderer for DefaultCell {
+    fn draw(&self, cell: &Cell) -> Vec<Primitive> {
+        let b = cell.bounds;
+        let font2 = cell.text_size;
+        let mut v = Vec::new();
+
+        let color = if cell.is_other_month {
+            BACKGROUND_COLOR
+        } else if cell.is_selected {
+            HIGH_LIGHT_COLOR
+        } else if cell.is_occurrence {
+            RECURRENCE_COLOR
+        } else if cell.is_holiday {
+            HOLIDAY_COLOR
+        } else {
+            BACKGROUND_COLOR
+        };
+
+        v.push(Primitive::Quad {
+            bounds: b,
+            background: Background::Color(color),
+            border_radius: 0,
+            border_width: 1,
+            border_color: BORDER_COLOR,
+        });
+
+        let x = b.center_x();
+        let y = b.center_y();
+
+        v.push(Primitive::Text {
+            content: cell.date.day().to_string(),
+            bounds: Rectangle { x, y, ..b },
+            color: if cell.is_other_month || cell.is_disabled {
+                SECOND_TEXT_COLOR
+            } else if cell.is_holiday {
+                HOLIDAY_TEXT_COLOR
+            } else {
+                FIRST_TEXT_COLOR
+            },
+            size: font2,
+            font: cell.font,
+            horizontal_alignment: HorizontalAlignment::Center,
+            vertical_alignment: VerticalAlignment::Center,
+        });
+
+        // a marker at the bottom of the cell if any event takes place
+        if cell.has_events {
+            let radius = b.width.min(b.height) / 16.0;
+            v.push(Primitive::Quad {
+                bounds: Rectangle {
+                    x: b.center_x() - radius,
+                    y: b.y + b.height - 4.0 * radius,
+                    width: 2.0 * radius,
+                    height: 2.0 * radius,
+                },
+                background: Background::Color(MARKER_COLOR),
+                border_radius: radius as u16,
+                border_width: 0,
+                border_color: Color::TRANSPARENT,
+            });
+        }
+
+        v
+    }
+}
+
+/// A field that can select a date
+pub struct DatePicker<'a, Message> {
+
+    /// the width of a month when the width shrinks
+    w: u32,
+    width: Length,
+    height: Length,
+    placeholder: String,
+    value: String,
+    on_change: Box<dyn Fn(String) -> Message>,
+    on_open: Option<Message>,
+    on_animation_start: Option<Message>,
+    on_close: Option<Message>,
+    on_cancel: Option<Message>,
+    on_month_change: Option<Box<dyn Fn(i32, u32) -> Message>>,
+    on_hover: Option<Box<dyn Fn(NaiveDate) -> Message>>,
+    on_select: Option<Box<dyn Fn(Option<NaiveDate>) -> Message>>,
+    close_on_select: bool,
+    padding: Option<u16>,
+    size: Option<u16>,
+    font: Font,
+    header_font: Font,
+    header_size: Option<u16>,
+    day_font: Font,
+    day_bold_font: Font,
+    day_size: Option<u16>,
+    footer_font: Font,
+    footer_size: Option<u16>,
+    months: u16,
+    arrangement: Arrangement,
+    scrolling: Option<u16>,
+    holidays: Option<&'a dyn HolidayProvider>,
+    events: Option<&'a dyn EventSource>,
+    recurrences: &'a [Recurrence],
+    on_recurrence: Option<Box<dyn Fn(Recurrence) -> Message>>,
+    cell_renderer: Box<dyn CellRenderer + 'a>,
+    is_disabled: Option<Box<dyn Fn(NaiveDate) -> bool + 'a>>,
+    parser: Box<dyn DateParser + 'a>,
+
+    /// the date taken as today, the local one if none
+    today: Option<NaiveDate>,
+    presets: Vec<Preset>,
+    on_preset: Option<Box<dyn Fn(Selection) -> Message>>,
+    state: &'a mut State,
+}
+
+impl<'a, Message> DatePicker<'a, Message>
+where
+    Message: Clone,
+{
+
+    /// create a new [`DatePicker`]
+    pub fn new<F>(
+        w: u32,
+        state: &'a mut State,
+        placeholder: &str,
+        value: &str,
+        on_change: F,
+    ) -> Self 
+    where
+        F: 'static + Fn(String) -> Message, 
+    {
+        Self { 
+            w,
+            width: Length::Shrink,
+            height: Length::Shrink,
+            state,
+            placeholder: String::from(placeholder),
+            value: String::from(value),
+            on_change: Box::new(on_change),
+            on_open: None,
+            on_animation_start: None,
+            on_close: None,
+            on_cancel: None,
+            on_month_change: None,
+            on_hover: None,
+            on_select: None,
+            close_on_select: true,
+            padding: None,
+            size: None,
+            font: Font::default(),
+            header_font: Font::default(),
+            header_size: None,
+            day_font: Font::default(),
+            day_bold_font: Font::default(),
+            day_size: None,
+            footer_font: Font::default(),
+            footer_size: None,
+            months: 1,
+            arrangement: Arrangement::Row,
+            scrolling: None,
+            holidays: None,
+            events: None,
+            recurrences: &[],
+            on_recurrence: None,
+            cell_renderer: Box::new(DefaultCell),
+            is_disabled: None,
+            parser: Box::new(parsing::standard()),
+            today: None,
+            presets: Vec::new(),
+            on_preset: None,
+        }
+    }
+
+    /// format date 2020-06-05
+    pub fn format_date(&self) -> String{
+        let dt = Utc.ymd(
+            self.state.year,
+            self.state.month,
+            self.state.day);
+        dt.format("%Y-%m-%d").to_string()
+    }
+
+    /// Formerly the message sent when the calendar opened or closed,
+    /// on which the host toggled `state.is_focused`.
+    /// The picker opens and closes itself, and no longer sends it
+    #[deprecated(note = "the picker opens and closes itself, use `on_open` and `on_close`")]
+    pub fn on_focus(self, _msg: Message) -> Self {
+        self
+    }
+
+    /// Sets the message sent when the input is clicked to open the calendar
+    pub fn on_open(mut self, msg: Message) -> Self {
+        self.on_open = Some(msg);
+        self
+    }
+
+    /// Sets the message sent when the calendar is closed,
+    /// by the input, a selection, Escape or a click outside
+    pub fn on_close(mut self, msg: Message) -> Self {
+        self.on_close = Some(msg);
+        self
+    }
+
+    /// Sets the message sent when an animation starts, when the months
+    /// change or the calendar opens or closes: iced reads the subscriptions
+    /// again after a message, and the host subscribes to the ticks
+    /// while [`State::is_animating`]
+    ///
+    /// [`State::is_animating`]: struct.State.html#method.is_animating
+    pub fn on_animation_start(mut self, msg: Message) -> Self {
+        self.on_animation_start = Some(msg);
+        self
+    }
+
+    /// Controls the [`DatePicker`] by the date of the host, given on every view:
+    /// the state shows it again whenever it changes, while navigating
+    /// the months leaves it selected until a day is picked.
+    /// A nullable date may be `None`
+    pub fn date<D: Into<Option<NaiveDate>>>(self, date: D) -> Self {
+        let date = date.into();
+
+        if self.state.committed != Some(date) {
+            self.state.commit(date);
+        }
+        self
+    }
+
+    /// Makes the date nullable, sending the picked date, or `None` when
+    /// it is cleared by the × of the input or Clear in the footer.
+    /// The picks are sent to `on_change` too
+    pub fn nullable<F>(mut self, on_select: F) -> Self
+    where
+        F: 'static + Fn(Option<NaiveDate>) -> Message,
+    {
+        self.on_select = Some(Box::new(on_select));
+        self
+    }
+
+    /// Sets whether picking a day closes the calendar, as by default.
+    /// It stays open to build a recurrence
+    pub fn close_on_select(mut self, close_on_select: bool) -> Self {
+        self.close_on_select = close_on_select;
+        self
+    }
+
+    /// Sets the message sent when the calendar is dismissed
+    /// by Escape or a click outside, before it closes
+    pub fn on_cancel(mut self, msg: Message) -> Self {
+        self.on_cancel = Some(msg);
+        self
+    }
+
+    /// Sets the handler of the first shown month when it changes,
+    /// e.g. to load the events of the new months
+    pub fn on_month_change<F>(mut self, on_month_change: F) -> Self
+    where
+        F: 'static + Fn(i32, u32) -> Message,
+    {
+        self.on_month_change = Some(Box::new(on_month_change));
+        self
+    }
+
+    /// Sets the handler of the day under the cursor,
+    /// called when the cursor moves onto another day
+    pub fn on_hover<F>(mut self, on_hover: F) -> Self
+    where
+        F: 'static + Fn(NaiveDate) -> Message,
+    {
+        self.on_hover = Some(Box::new(on_hover));
+        self
+    }
+
+    /// Sets the width of the [`DatePicker`]. The months share it,
+    /// each `w` wide when it shrinks
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the height of the open [`DatePicker`], the cells
+    /// share what the input leaves. They are square when it shrinks
+    pub fn height(mut self, height: Length) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets the padding of the [`DatePicker`].
+    pub fn padding(mut self, padding: u16) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Sets the text size of the [`DatePicker`].
+    pub fn size(mut self, size: u16) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Sets the font of the input text.
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Sets the font of the weekdays and month names.
+    pub fn header_font(mut self, font: Font) -> Self {
+        self.header_font = font;
+        self
+    }
+
+    /// Sets the text size of the weekdays and month names,
+    /// relative to the cells by default
+    pub fn header_size(mut self, size: u16) -> Self {
+        self.header_size = Some(size);
+        self
+    }
+
+    /// Sets the font of the day numbers.
+    pub fn day_font(mut self, font: Font) -> Self {
+        self.day_font = font;
+        self
+    }
+
+    /// Sets the font of the numbers of today and the selected day,
+    /// e.g. a bold variant of the day font
+    pub fn day_bold_font(mut self, font: Font) -> Self {
+        self.day_bold_font = font;
+        self
+    }
+
+    /// Sets the text size of the day numbers,
+    /// relative to the cells by default
+    pub fn day_size(mut self, size: u16) -> Self {
+        self.day_size = Some(size);
+        self
+    }
+
+    /// Sets the font of the footer and the recurrence controls.
+    pub fn footer_font(mut self, font: Font) -> Self {
+        self.footer_font = font;
+        self
+    }
+
+    /// Sets the text size of the footer and the recurrence controls,
+    /// relative to the cells by default
+    pub fn footer_size(mut self, size: u16) -> Self {
+        self.footer_size = Some(size);
+        self
+    }
+
+    /// Sets how many consecutive months are shown at once.
+    /// Pre and Next still move by one month
+    pub fn months(mut self, months: u16) -> Self {
+        self.months = months.max(1);
+        self
+    }
+
+    /// Sets whether the months are placed in a row or a column
+    pub fn arrangement(mut self, arrangement: Arrangement) -> Self {
+        self.arrangement = arrangement;
+        self
+    }
+
+    /// Replaces the pages of months by a continuous list of months,
+    /// scrolled with the mouse wheel, `visible` months high.
+    /// Only the visible months are drawn
+    pub fn scrolling(mut self, visible: u16) -> Self {
+        self.scrolling = Some(visible.max(1));
+        self
+    }
+
+    /// Sets the [`HolidayProvider`] whose holidays are
+    /// highlighted in the calendar
+    pub fn holidays(mut self, provider: &'a dyn HolidayProvider) -> Self {
+        self.holidays = Some(provider);
+        self
+    }
+
+    /// Sets the [`EventSource`] whose events are marked
+    /// in the calendar
+    pub fn events(mut self, source: &'a dyn EventSource) -> Self {
+        self.events = Some(source);
+        self
+    }
+
+    /// Sets the [`Recurrence`]s whose occurrences are
+    /// highlighted in the calendar
+    pub fn recurrences(mut self, recurrences: &'a [Recurrence]) -> Self {
+        self.recurrences = recurrences;
+        self
+    }
+
+    /// Turns the [`DatePicker`] into a recurrence builder: once a start
+    /// date is picked, frequency, interval and weekday controls show up
+    /// under the calendar, and every change sends the built [`Recurrence`]
+    pub fn recurrence_builder<F>(mut self, on_recurrence: F) -> Self
+    where
+        F: 'static + Fn(Recurrence) -> Message,
+    {
+        self.on_recurrence = Some(Box::new(on_recurrence));
+        self
+    }
+
+    /// Sets the [`CellRenderer`] drawing the day cells,
+    /// e.g. a closure adding a price under each day
+    pub fn cell_renderer<R>(mut self, renderer: R) -> Self
+    where
+        R: 'a + CellRenderer,
+    {
+        self.cell_renderer = Box::new(renderer);
+        self
+    }
+
+    /// Sets which days can't be picked: they are greyed
+    /// and clicking them does nothing
+    pub fn disabled<F>(mut self, is_disabled: F) -> Self
+    where
+        F: 'a + Fn(NaiveDate) -> bool,
+    {
+        self.is_disabled = Some(Box::new(is_disabled));
+        self
+    }
+
+    /// Sets the [`DateParser`] of the text typed while the calendar is open,
+    /// the picker's format and relative expressions by default
+    pub fn parser<P>(mut self, parser: P) -> Self
+    where
+        P: 'a + DateParser,
+    {
+        self.parser = Box::new(parser);
+        self
+    }
+
+    /// Sets the date taken as today, for the presets, the typed
+    /// expressions and the highlighted day
+    pub fn today(mut self, today: NaiveDate) -> Self {
+        self.today = Some(today);
+        self
+    }
+
+    /// Shows a side panel of presets, e.g. [`Preset::defaults`],
+    /// sending the date or range of the clicked one
+    pub fn presets<F>(mut self, presets: Vec<Preset>, on_preset: F) -> Self
+    where
+        F: 'static + Fn(Selection) -> Message,
+    {
+        self.presets = presets;
+        self.on_preset = Some(Box::new(on_preset));
+        self
+    }
+
+    /// the date taken as today
+    fn date_today(&self) -> NaiveDate {
+        self.today.unwrap_or_else(|| Local::today().naive_local())
+    }
+
+    /// how many cells wide the panel of presets is
+    fn panel_cells(&self) -> f32 {
+        if self.presets.is_empty() { 0.0 } else { 3.0 }
+    }
+
+    /// which part of the calendar the panel of presets is: the last one
+    fn panel_index(&self) -> usize {
+        self.grid_count() + if self.shows_pattern() { 2 } else { 1 }
+    }
+
+    /// check if the day can't be picked
+    fn is_disabled(&self, date: NaiveDate) -> bool {
+        match &self.is_disabled {
+            Some(is_disabled) => is_disabled(date),
+            None => false,
+        }
+    }
+
+    /// Where a click reaches the target, given the layout of the
+    /// [`DatePicker`] at the origin; none when the target isn't shown
+    pub fn position(&self, root: &layout::Node, target: Target) -> Option<Point> {
+        let bounds = root.bounds();
+        match target {
+            Target::Input => {
+                let input = root.children().first()?.bounds();
+                return Some(Point::new(input.center_x(), input.center_y()))
+            }
+            Target::ClearInput => {
+                let clear = self.clear_button(root.children().first()?.bounds())?;
+                return Some(Point::new(clear.center_x(), clear.center_y()))
+            }
+            Target::Outside => {
+                return Some(Point::new(bounds.x + bounds.width + 1.0, bounds.y + bounds.height + 1.0))
+            }
+            _ => {}
+        }
+
+        // the parts of the calendar are placed in the calendar
+        let calendar = match root.children().get(1) {
+            Some(calendar) if self.state.is_focused => calendar,
+            _ => return None,
+        };
+        let origin = calendar.bounds();
+        let part = |n: usize| {
+            calendar.children().get(n).map(|part| Rectangle {
+                x: origin.x + part.bounds().x,
+                y: origin.y + part.bounds().y,
+                ..part.bounds()
+            })
+        };
+
+        let date = match target {
+            Target::Input | Target::ClearInput | Target::Outside => return None,
+            Target::Preset(preset) => {
+                let row = self.presets.iter().position(|p| *p == preset)?;
+                let panel = part(self.panel_index())?;
+                let height = panel.height / self.calendar_rows();
+                return Some(Point::new(panel.center_x(), panel.y + (row as f32 + 0.5) * height))
+            }
+            Target::Clear => {
+                let clear = self.clear_action(part(self.grid_count())?)?;
+                return Some(Point::new(clear.center_x(), clear.center_y()))
+            }
+            Target::Previous | Target::Next if self.scrolling.is_some() => return None,
+            Target::Previous | Target::Next => {
+                let footer = part(self.grid_count())?;
+                let width = self.button_width(footer);
+                let x = if target == Target::Previous {
+                    footer.x + width / 2.0
+                } else {
+                    footer.x + footer.width - width / 2.0
+                };
+                return Some(Point::new(x, footer.center_y()))
+            }
+            Target::Day(day) => NaiveDate::from_ymd_opt(self.state.year, self.state.month, day)?,
+            Target::Date(date) => date,
+        };
+
+        if self.scrolling.is_some() {
+
+            // the month of the date in the list, under the scroll
+            let list = part(0)?;
+            let height = self.month_height(list);
+            let scroll = self.state.scroll as f32 / SCROLL_UNIT * height;
+            let n = (date.year() - self.state.year) * 12
+                + date.month() as i32 - self.state.month as i32;
+            let grid = Rectangle {
+                y: list.y + n as f32 * height - scroll,
+                height,
+                ..list
+            };
+            let point = self.cell_center(grid, date);
+
+            return if list.contains(point) { Some(point) } else { None }
+        }
+
+        (0..self.grid_count())
+            .find(|n| self.grid_month(*n) == (date.year(), date.month()))
+            .and_then(part)
+            .map(|grid| self.cell_center(grid, date))
+    }
+
+    /// Describes the [`DatePicker`] for assistive technologies:
+    /// the input, then when focused, the shown months as grids
+    /// of days, with the selected day focused, and the buttons
+    pub fn description(&self) -> Node {
+        let input = Node::new(Role::TextInput, if self.value.is_empty() {
+            &self.placeholder
+        } else {
+            &self.value
+        });
+
+        if !self.state.is_focused {
+            return Node::new(Role::Group, "").children(vec![input])
+        }
+
+        let (first, last) = self.shown_months();
+        let count = (last.0 - first.0) * 12 + last.1 as i32 - first.1 as i32 + 1;
+        let mut children: Vec<Node> = (0..count)
+            .map(|n| {
+                let (year, month) = add_months(first.0, first.1, n);
+                self.describe_grid(year, month)
+            })
+            .collect();
+
+        if self.scrolling.is_none() {
+            children.push(Node::new(Role::Button, "Previous month"));
+            children.push(Node::new(Role::Button, "Next month"));
+        }
+
+        let calendar = Node::new(Role::Dialog, &accessibility::months_label(first, last))
+            .children(children);
+
+        Node::new(Role::Group, "").children(vec![input, calendar])
+    }
+
+    /// describe the grid of a month: a row of weekdays,
+    /// then six weeks of days
+    fn describe_grid(&self, year: i32, month: u32) -> Node {
+        let first = NaiveDate::from_ymd(year, month, 1);
+        let start = week_start(first);
+        let today = self.date_today();
+        let focused = self.state.value();
+
+        // the holidays of the months the grid shows days of
+        let holidays: Vec<Holiday> = (-1..=1)
+            .flat_map(|n| {
+                let (year, month) = add_months(year, month, n);
+                self.month_holidays(year, month)
+            })
+            .collect();
+
+        let header = Node::new(Role::Row, "").children(WEEKDAYS
+            .iter()
+            .map(|&weekday| {
+                let date = NaiveDate::from_isoywd(2020, 1, weekday);
+                Node::new(Role::ColumnHeader, &date.format("%A").to_string())
+            })
+            .collect());
+
+        let weeks = (0..6).map(|week| {
+            Node::new(Role::Row, "").children((0..7)
+                .map(|weekday| {
+                    let date = start + chrono::Duration::days(week * 7 + weekday);
+
+                    // the days of the other months aren't drawn selected,
+                    // the last selected day alone is focused
+                    let is_shown = date.year() == year && date.month() == month;
+                    let is_selected = is_shown && self.state.is_selected(date);
+                    let is_focused = is_shown && Some(date) == focused;
+                    let holiday = holidays.iter().find(|h| h.date == date);
+                    let events = self.events
+                        .map(|source| source.events_on(date).len())
+                        .unwrap_or(0);
+
+                    let node = Node {
+                        is_focused,
+                        is_selected,
+                        ..Node::new(Role::GridCell, &accessibility::day_label(
+                            date,
+                            is_selected,
+                            date == today,
+                            holiday.is_some(),
+                            events,
+                        ))
+                    };
+
+                    match holiday {
+                        Some(holiday) => node.description(&holiday.name),
+                        None => node,
+                    }
+                })
+                .collect())
+        });
+
+        Node::new(Role::Grid, &accessibility::months_label((year, month), (year, month)))
+            .children(std::iter::once(header).chain(weeks).collect())
+    }
+
+    /// check if the recurrence controls are shown
+    fn shows_pattern(&self) -> bool {
+        self.on_recurrence.is_some() && self.state.pattern.start.is_some()
+    }
+
+    /// send the recurrence built so far
+    fn emit_recurrence(&self, messages: &mut Vec<Message>) {
+        if let (Some(on_recurrence), Some(recurrence)) =
+            (&self.on_recurrence, self.state.recurrence()) {
+            messages.push(on_recurrence(recurrence));
+        }
+    }
+
+    /// the days of the month on which any recurrence occurs,
+    /// including the one being built
+    fn month_occurrences(&self, year: i32, month: u32) -> Vec<u32> {
+        let days = days_in_month(year, month);
+        let first = NaiveDate::from_ymd(year, month, 1);
+        let last = NaiveDate::from_ymd(year, month, days);
+
+        let preview = if self.on_recurrence.is_some() {
+            self.state.recurrence()
+        } else {
+            None
+        };
+
+        self.recurrences
+            .iter()
+            .chain(preview.iter())
+            .flat_map(|recurrence| recurrence.occurrences_between(first, last))
+            .map(|date| date.day())
+            .collect()
+    }
+
+    /// the holidays of the month
+    fn month_holidays(&self, year: i32, month: u32) -> Vec<Holiday> {
+        self.holidays
+            .map(|provider| provider.holidays(year, month))
+            .unwrap_or_default()
+    }
+
+    /// the text size of the weekdays and month names in cells of the side
+    fn header_text_size(&self, side: f32) -> f32 {
+        self.header_size.map_or(HEADER_TEXT_RATIO * side, f32::from)
+    }
+
+    /// the text size of the day numbers in cells of the side
+    fn day_text_size(&self, side: f32) -> f32 {
+        self.day_size.map_or(DAY_TEXT_RATIO * side, f32::from)
+    }
+
+    /// the text size of the footer in cells of the side
+    fn footer_text_size(&self, side: f32) -> f32 {
+        self.footer_size.map_or(HEADER_TEXT_RATIO * side, f32::from)
+    }
+
+    /// how many month grids are side by side
+    fn grids_across(&self) -> f32 {
+        match (self.scrolling, self.arrangement) {
+            (None, Arrangement::Row) => self.months as f32,
+            _ => 1.0,
+        }
+    }
+
+    /// how many rows of cells the calendar has: the grids,
+    /// or the visible months of the list, the footer
+    /// and the recurrence controls
+    fn calendar_rows(&self) -> f32 {
+        let grids = match (self.scrolling, self.arrangement) {
+            (Some(visible), _) => visible as f32,
+            (None, Arrangement::Column) => self.months as f32,
+            (None, Arrangement::Row) => 1.0,
+        };
+        let pattern = if self.shows_pattern() { 2.0 } else { 0.0 };
+
+        grids * self.grid_rows() + 1.0 + pattern
+    }
+
+    /// the height of a month in the list of months
+    fn month_height(&self, bounds: Rectangle) -> f32 {
+        bounds.height / self.scrolling.unwrap_or(1) as f32
+    }
+
+    /// the width of the Pre and Next buttons of the footer: two cells
+    fn button_width(&self, footer: Rectangle) -> f32 {
+        2.0 * footer.width / (7.0 * self.grids_across())
+    }
+
+    /// how many rows a month grid takes: the weekdays and six weeks,
+    /// plus the name of the month when several months are shown
+    fn grid_rows(&self) -> f32 {
+        if self.shows_month_names() { 8.0 } else { 7.0 }
+    }
+
+    /// check if each grid shows the name of its month
+    fn shows_month_names(&self) -> bool {
+        self.months > 1 || self.scrolling.is_some()
+    }
+
+    /// how many parts of the calendar show months:
+    /// one list of months when scrolling, else one per grid
+    fn grid_count(&self) -> usize {
+        if self.scrolling.is_some() { 1 } else { self.months as usize }
+    }
+
+    /// the year and month shown in the nth grid
+    fn grid_month(&self, n: usize) -> (i32, u32) {
+        let offset = self.state.offset.min(self.months as u32 - 1);
+        add_months(self.state.year, self.state.month, n as i32 - offset as i32)
+    }
+
+    /// move to the previous month, keeping the day if the month has it
+    fn previous_month(&mut self) {
+        let shown = self.grid_month(0);
+        let (year, month) = add_months(self.state.year, self.state.month, -1);
+        self.state.year = year;
+        self.state.month = month;
+
+        // if last month doesn't have current day
+        let last_month_days = days_in_month(year, month);
+        if self.state.day > last_month_days {
+
+            self.state.day = last_month_days;
+        }
+
+        self.months_changed(shown);
+    }
+
+    /// move to the next month, keeping the day if the month has it
+    fn next_month(&mut self) {
+        let shown = self.grid_month(0);
+        let (year, month) = add_months(self.state.year, self.state.month, 1);
+        self.state.year = year;
+        self.state.month = month;
+
+        // if next month doesn't have current day
+        let next_month_days = days_in_month(year, month);
+        if self.state.day > next_month_days {
+
+            self.state.day = next_month_days;
+        }
+
+        self.months_changed(shown);
+    }
+
+    /// move to the previous year, keeping the day if the month has it
+    fn previous_year(&mut self) {
+        let shown = self.grid_month(0);
+        self.state.year -= 1;
+
+        // if the month doesn't have current day, e.g. 29 February
+        let days = days_in_month(self.state.year, self.state.month);
+        if self.state.day > days {
+
+            self.state.day = days;
+        }
+
+        self.months_changed(shown);
+    }
+
+    /// move to the next year, keeping the day if the month has it
+    fn next_year(&mut self) {
+        let shown = self.grid_month(0);
+        self.state.year += 1;
+
+        // if the month doesn't have current day, e.g. 29 February
+        let days = days_in_month(self.state.year, self.state.month);
+        if self.state.day > days {
+
+            self.state.day = days;
+        }
+
+        self.months_changed(shown);
+    }
+
+    /// the shown months changed from the given first one:
+    /// announce the new ones, and animate them from the next tick
+    fn months_changed(&mut self, from: (i32, u32)) {
+        self.state.announcement = Some(self.shown_months());
+
+        if self.state.animation != Animation::None && self.scrolling.is_none() {
+            self.state.months = Some(Transition { start: None, from });
+        }
+    }
+
+    /// the first and the last shown months,
+    /// the visible ones of the list when scrolling
+    fn shown_months(&self) -> ((i32, u32), (i32, u32)) {
+        let (first, count) = match self.scrolling {
+            Some(visible) => {
+                ((self.state.scroll as f32 / SCROLL_UNIT).floor() as i32, visible as i32)
+            }
+            None => (-(self.state.offset.min(self.months as u32 - 1) as i32), self.months as i32),
+        };
+
+        (
+            add_months(self.state.year, self.state.month, first),
+            add_months(self.state.year, self.state.month, first + count - 1),
+        )
+    }
+
+    /// Draw the nth month grid while the months are animated:
+    /// the previous month slides or fades out as the new one comes in.
+    /// Holidays have no tooltip until it is over
+    fn draw_transition(
+        &self,
+        v: &mut Vec<Primitive>,
+        bound: Rectangle,
+        n: usize,
+        transition: Transition<(i32, u32)>,
+    ) {
+        let p = transition.progress(self.state.clock);
+        let (year, month) = add_months(transition.from.0, transition.from.1, n as i32);
+        let (new_year, new_month) = self.grid_month(n);
+        let nowhere = Point::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+        let mut old = Vec::new();
+        let mut new = Vec::new();
+        if self.state.animation == Animation::Slide {
+
+            // going forward, the months come in from the right
+            let shift = if (new_year, new_month) > (year, month) {
+                bound.width
+            } else {
+                -bound.width
+            };
+            let old_bound = Rectangle { x: bound.x - p * shift, ..bound };
+            let new_bound = Rectangle { x: bound.x + (1.0 - p) * shift, ..bound };
+
+            self.draw_grid(&mut old, old_bound, year, month, nowhere, &mut None);
+            self.draw_grid(&mut new, new_bound, new_year, new_month, nowhere, &mut None);
+        } else {
+            self.draw_grid(&mut old, bound, year, month, nowhere, &mut None);
+            self.draw_grid(&mut new, bound, new_year, new_month, nowhere, &mut None);
+            old = vec![fade(Primitive::Group { primitives: old }, 1.0 - p)];
+            new = vec![fade(Primitive::Group { primitives: new }, p)];
+        }
+
+        old.append(&mut new);
+        v.push(Primitive::Clip {
+            bounds: bound,
+            offset: Vector::new(0, 0),
+            content: Box::new(Primitive::Group {
+                primitives: old
+            }),
+        });
+    }
+
+    /// Draw a month grid: its name when several months are shown,
+    /// the weekdays menu, then the days of the last, current
+    /// and next month
+    fn draw_grid(
+        &self,
+        v: &mut Vec<Primitive>,
+        bound: Rectangle,
+        year: i32,
+        month: u32,
+        cursor_position: Point,
+        hovered_holiday: &mut Option<(Rectangle, String)>,
+    ) {
+        let (width, height) = (bound.width/7.0, bound.height/self.grid_rows());
+        let font1 = self.header_text_size(width.min(height));
+
+        // Draw the name of the month
+        let mut top = bound.y;
+        if self.shows_month_names() {
+            let b = Rectangle {
+                x: bound.x,
+                y: top,
+                width: bound.width,
+                height,
+            };
+
+            let x = b.center_x();
+            let y = b.center_y();
+
+            v.push(Primitive::Text {
+                content: Utc.ymd(year, month, 1).format("%B %Y").to_string(),
+                bounds: Rectangle { x, y, ..b },
+                color: FIRST_TEXT_COLOR,
+                size: font1,
+                font: self.header_font,
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+
+            top += height;
+        }
+
+        // Draw the weekdays menu
+        // Mon, Tue, Web, Thu, Fri, Sat, Sun
+        for weekday in 0..7 {
+            let b = Rectangle {
+                x: bound.x + weekday as f32 * width,
+                y: top,
+                width,
+                height,
+            };
+
+            let x = b.center_x();
+            let y = b.center_y();
+            
+            v.push(Primitive::Quad {
+                bounds: b,
+                background: Background::Color(BACKGROUND_COLOR),
+                border_radius: 0,
+                border_width: 1,
+                border_color: BORDER_COLOR,
+            });
+
+            v.push(Primitive::Text {
+                content: WEEK_DAYS[weekday].to_string(),
+                bounds: Rectangle { x, y, ..b },
+                color: FIRST_TEXT_COLOR,
+                size: font1,
+                font: self.header_font,
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Center,
+            }) 
+        }
+
+        // first day of the current month
+        let first = NaiveDate::from_ymd(year, month, 1);
+
+        // the grid starts on the Monday before it
+        let start = week_start(first);
+        let today = self.date_today();
+
+        // holidays of current month
+        let holidays = self.month_holidays(year, month);
+
+        // days on which a recurrence occurs
+        let occurrences = self.month_occurrences(year, month);
+
+        // Draw the days of last, current and next month
+        for i in 0..42 {
+            let date = start + chrono::Duration::days(i);
+            let is_other_month = date.month() != month;
+            let b = Rectangle {
+                x: bound.x + (i % 7) as f32 * width,
+                y: top + (i / 7 + 1) as f32 * height,
+                width,
+                height,
+            };
+
+            let holiday = holidays.iter().find(|h| !is_other_month && h.date == date);
+            let has_events = match self.events {
+                Some(source) => !source.events_on(date).is_empty(),
+                None => false,
+            };
+
+            let is_selected = !is_other_month && self.state.is_selected(date);
+            let cell = Cell {
+                date,
+                bounds: b,
+                is_selected,
+                is_hovered: b.contains(cursor_position),
+                is_disabled: self.is_disabled(date),
+                is_other_month,
+                is_today: date == today,
+                is_holiday: holiday.is_some(),
+                is_occurrence: !is_other_month && occurrences.contains(&date.day()),
+                has_events,
+                font: if is_selected || (!is_other_month && date == today) {
+                    self.day_bold_font
+                } else {
+                    self.day_font
+                },
+                text_size: self.day_text_size(width.min(height)),
+            };
+
+            v.extend(self.cell_renderer.draw(&cell));
+
+            if let Some(holiday) = holiday {
+                if cell.is_hovered {
+                    *hovered_holiday = Some((b, holiday.name.clone()));
+                }
+            }
+        }
+    }
+
+    /// Draw the visible part of the continuous list of months,
+    /// keeping the name of the month at the top in place
+    fn draw_scroll(
+        &self,
+        v: &mut Vec<Primitive>,
+        bound: Rectangle,
+        cursor_position: Point,
+        hovered_holiday: &mut Option<(Rectangle, String)>,
+    ) {
+        let height = self.month_height(bound);
+        let size = height / self.grid_rows();
+        let font1 = self.header_text_size(size.min(bound.width/7.0));
+        let scroll = self.state.scroll as f32 / SCROLL_UNIT * height;
+
+        // the visible months, counted from the month of the selected date
+        let first = (scroll / height).floor() as i32;
+        let last = ((scroll + bound.height) / height).ceil() as i32;
+
+        let mut primitives = Vec::new();
+        let mut hovered = None;
+        for n in first..last {
+            let (year, month) = add_months(self.state.year, self.state.month, n);
+            let b = Rectangle {
+                x: bound.x,
+                y: bound.y + n as f32 * height - scroll,
+                width: bound.width,
+                height,
+            };
+
+            self.draw_grid(&mut primitives, b, year, month, cursor_position, &mut hovered);
+        }
+
+        // the hidden part of the list has no tooltip
+        if bound.contains(cursor_position) {
+            *hovered_holiday = hovered;
+        }
+
+        // Draw the name of the month at the top over the list
+        {
+            let (year, month) = add_months(self.state.year, self.state.month, first);
+            let b = Rectangle {
+                height: size,
+                ..bound
+            };
+
+            primitives.push(Primitive::Quad {
+                bounds: b,
+                background: Background::Color(Color::WHITE),
+                border_radius: 0,
+                border_width: 1,
+                border_color: BORDER_COLOR,
+            });
+
+            let x = b.center_x();
+            let y = b.center_y();
+
+            primitives.push(Primitive::Text {
+                content: Utc.ymd(year, month, 1).format("%B %Y").to_string(),
+                bounds: Rectangle { x, y, ..b },
+                color: FIRST_TEXT_COLOR,
+                size: font1,
+                font: self.header_font,
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+        }
+
+        v.push(Primitive::Clip {
+            bounds: bound,
+            offset: Vector::new(0, 0),
+            content: Box::new(Primitive::Group {
+                primitives
+            }),
+        });
+    }
+
+    /// Draw the footer: the control buttons on both ends,
+    /// Pre and Next operation, and the selected date between them
+    fn draw_footer(&self, v: &mut Vec<Primitive>, bound: Rectangle) {
+        let size = bound.height;
+        let width = self.button_width(bound);
+        let font1 = self.footer_text_size(size.min(width / 2.0));
+
+        // the typed date, greyed if it can't be picked, "Unknown date"
+        // until the text is understood, the selected date,
+        // or "No date" once cleared
+        let is_typed = matches!(&self.state.draft, Some(draft) if !draft.trim().is_empty());
+        let (label, color) = match self.state.preview {
+            Some(date) if self.is_disabled(date) => (date.format("%Y-%m-%d").to_string(), SECOND_TEXT_COLOR),
+            Some(date) => (date.format("%Y-%m-%d").to_string(), FIRST_TEXT_COLOR),
+            None if is_typed => (String::from("Unknown date"), SECOND_TEXT_COLOR),
+            None if self.state.is_empty => (String::from("No date"), SECOND_TEXT_COLOR),
+            None => (self.state.date().format("%Y-%m-%d").to_string(), FIRST_TEXT_COLOR),
+        };
+
+        // Draw the Clear action, greyed without a date
+        let clear = self.clear_action(bound);
+        if let Some(b) = clear {
+            let x = b.center_x();
+            let y = b.center_y();
+
+            v.push(Primitive::Text {
+                content: String::from("Clear"),
+                bounds: Rectangle { x, y, ..b },
+                color: if self.state.is_empty { SECOND_TEXT_COLOR } else { FIRST_TEXT_COLOR },
+                size: 0.8 * font1,
+                font: self.footer_font,
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+        }
+        let clear_width = clear.map_or(0.0, |b| b.width);
+
+        // the list of months is scrolled instead
+        if self.scrolling.is_some() {
+            let x = bound.center_x();
+            let y = bound.center_y();
+
+            v.push(Primitive::Text {
+                content: label,
+                bounds: Rectangle { x, y, ..bound },
+                color,
+                size: font1,
+                font: self.footer_font,
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+            return
+        }
+
+        // Draw the select date label
+        {
+            let b = Rectangle {
+                x: bound.x + width,
+                y: bound.y,
+                width: bound.width - 2.0 * width - clear_width,
+                height: size,
+            }; 
+            
+            let x = b.center_x();
+            let y = b.center_y();
+
+            v.push(Primitive::Text {
+                content: label,
+                bounds: Rectangle { x, y, ..b },
+                color,
+                size: font1,
+                font: self.footer_font,
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+        }
+
+        // Draw the control buttons
+        let mut b = Rectangle {
+            x: bound.x,
+            y: bound.y,
+            width,
+            height: size,
+        }; 
+        
+        v.push(Primitive::Quad {
+            bounds: b,
+            background: Background::Color(HIGH_LIGHT_COLOR),
+            border_radius: 0,
+            border_width: 1,
+            border_color: BORDER_COLOR,
+        });
+
+        let mut x = b.center_x();
+        let mut y = b.center_y();
+
+        v.push(Primitive::Text {
+            content: String::from("Pre"),
+            bounds: Rectangle { x, y, ..b },
+            color: FIRST_TEXT_COLOR,
+            size: font1,
+            font: self.footer_font,
+            horizontal_alignment: HorizontalAlignment::Center,
+            vertical_alignment: VerticalAlignment::Center,
+        });
+
+        b.x = bound.x + bound.width - width;
+
+        v.push(Primitive::Quad {
+            bounds: b,
+            background: Background::Color(HIGH_LIGHT_COLOR),
+            border_radius: 0,
+            border_width: 1,
+            border_color: BORDER_COLOR,
+        });
+
+        x = b.center_x();
+        y = b.center_y();
+
+        v.push(Primitive::Text {
+            content: String::from("Next"),
+            bounds: Rectangle {x, y, ..b},
+            color: FIRST_TEXT_COLOR,
+            size: font1,
+            font: self.footer_font,
+            horizontal_alignment: HorizontalAlignment::Center,
+            vertical_alignment: VerticalAlignment::Center,
+        });
+    }
+
+    /// Draw the presets, a row each, the one of the selection highlighted
+    fn draw_presets(&self, v: &mut Vec<Primitive>, bound: Rectangle) {
+        let height = bound.height / self.calendar_rows();
+        let font1 = self.footer_text_size(height.min(bound.width / self.panel_cells()));
+        let selection = self.state.selection();
+        let today = self.date_today();
+
+        for (row, preset) in self.presets.iter().enumerate() {
+            let b = Rectangle {
+                y: bound.y + row as f32 * height,
+                height,
+                ..bound
+            };
+
+            v.push(Primitive::Quad {
+                bounds: b,
+                background: Background::Color(if Some(preset.selection(today)) == selection {
+                    HIGH_LIGHT_COLOR
+                } else {
+                    BACKGROUND_COLOR
+                }),
+                border_radius: 0,
+                border_width: 1,
+                border_color: BORDER_COLOR,
+            });
+
+            v.push(Primitive::Text {
+                content: preset.label(),
+                bounds: Rectangle { x: b.x + font1 / 2.0, y: b.center_y(), ..b },
+                color: FIRST_TEXT_COLOR,
+                size: font1,
+                font: self.footer_font,
+                horizontal_alignment: HorizontalAlignment::Left,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+        }
+    }
+
+    /// Draw the recurrence controls:
+    /// Day, Week, Month, Year, -, interval, +
+    /// then a toggle for each weekday
+    fn draw_pattern(&self, v: &mut Vec<Primitive>, bound: Rectangle) {
+        let (width, height) = (bound.width / 7.0, bound.height / 2.0);
+        let font1 = self.footer_text_size(width.min(height));
+
+        let pattern = self.state.pattern;
+        let interval = pattern.interval.to_string();
+
+        for column in 0..7 {
+            let b = Rectangle {
+                x: bound.x + column as f32 * width,
+                y: bound.y,
+                width,
+                height,
+            };
+
+            let (content, color) = match column {
+                0..=3 => (FREQUENCY_LABELS[column], if FREQUENCIES[column] == pattern.frequency {
+                    HIGH_LIGHT_COLOR
+                } else {
+                    BACKGROUND_COLOR
+                }),
+                4 => ("-", HIGH_LIGHT_COLOR),
+                5 => (interval.as_str(), Color::WHITE),
+                _ => ("+", HIGH_LIGHT_COLOR),
+            };
+
+            v.push(Primitive::Quad {
+                bounds: b,
+                background: Background::Color(color),
+                border_radius: 0,
+                border_width: 1,
+                border_color: BORDER_COLOR,
+            });
+
+            let x = b.center_x();
+            let y = b.center_y();
+
+            v.push(Primitive::Text {
+                content: content.to_string(),
+                bounds: Rectangle { x, y, ..b },
+                color: FIRST_TEXT_COLOR,
+                size: font1,
+                font: self.footer_font,
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+        }
+
+        for (column, label) in WEEK_DAYS.iter().enumerate() {
+            let b = Rectangle {
+                x: bound.x + column as f32 * width,
+                y: bound.y + height,
+                width,
+                height,
+            };
+
+            let is_selected = pattern.has_weekdays()
+                && pattern.weekdays & (1 << column) != 0;
+
+            v.push(Primitive::Quad {
+                bounds: b,
+                background: Background::Color(if is_selected {
+                    HIGH_LIGHT_COLOR
+                } else {
+                    BACKGROUND_COLOR
+                }),
+                border_radius: 0,
+                border_width: 1,
+                border_color: BORDER_COLOR,
+            });
+
+            let x = b.center_x();
+            let y = b.center_y();
+
+            v.push(Primitive::Text {
+                content: label.to_string(),
+                bounds: Rectangle { x, y, ..b },
+                color: if pattern.has_weekdays() {
+                    FIRST_TEXT_COLOR
+                } else {
+                    SECOND_TEXT_COLOR
+                },
+                size: font1,
+                font: self.footer_font,
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+        }
+    }
+
+    /// the date of the cell under the cursor in the grid of a month,
+    /// and how many months away from the grid's month it is
+    fn date_at(
+        &self,
+        year: i32,
+        month: u32,
+        bounds: Rectangle,
+        cursor_position: Point,
+    ) -> Option<(i32, (i32, u32), u32)> {
+        let (width, height) = (bounds.width / 7.0, bounds.height / self.grid_rows());
+
+        // the days start under the name of the month and the weekdays menu
+        let top = bounds.y + (self.grid_rows() - 6.0) * height;
+        if cursor_position.y < top {
+            return None
+        }
+
+        let column = (((cursor_position.x - bounds.x) / width) as i32).min(6);
+        let row = (((cursor_position.y - top) / height) as i32).min(5);
+        let cell = row * 7 + column;
+
+        let weekday = Utc.ymd(year, month, 1).weekday().num_days_from_monday() as i32;
+        let days = days_in_month(year, month) as i32;
+
+        let (shift, (year, month), day) = if cell < weekday {
+
+            // if click the date in the last month
+            let (last_year, last_month) = add_months(year, month, -1);
+            let last_month_days = days_in_month(last_year, last_month) as i32;
+            (-1, (last_year, last_month), last_month_days - (weekday - cell - 1))
+        } else if cell < weekday + days {
+
+            // if click the date in the current month
+            (0, (year, month), cell - weekday + 1)
+        } else {
+
+            // if click the date in the next month
+            (1, add_months(year, month, 1), cell - weekday - days + 1)
+        };
+
+        Some((shift, (year, month), day as u32))
+    }
+
+    /// the center of the cell of a date in the grid of its month
+    fn cell_center(&self, bounds: Rectangle, date: NaiveDate) -> Point {
+        let (width, height) = (bounds.width / 7.0, bounds.height / self.grid_rows());
+        let top = bounds.y + (self.grid_rows() - 6.0) * height;
+
+        let weekday = date.with_day(1).unwrap_or(date).weekday().num_days_from_monday();
+        let cell = weekday + date.day() - 1;
+
+        Point::new(
+            bounds.x + ((cell % 7) as f32 + 0.5) * width,
+            top + ((cell / 7) as f32 + 0.5) * height,
+        )
+    }
+
+    /// select the date and send it
+    fn select(&mut self, year: i32, month: u32, day: u32, messages: &mut Vec<Message>) {
+        self.state.year = year;
+        self.state.month = month;
+        self.state.day = day;
+
+        self.state.is_empty = false;
+        self.state.range = None;
+        self.state.draft = None;
+        self.state.preview = None;
+        self.state.before_preview = None;
+
+        // the host may refuse the date, it is synced again then
+        if self.state.committed.is_some() {
+            self.state.committed = Some(NaiveDate::from_ymd_opt(year, month, day));
+        }
+
+        let message = (self.on_change)(self.format_date());
+        messages.push(message);
+
+        if let Some(on_select) = &self.on_select {
+            messages.push(on_select(Some(self.state.date())));
+        }
+
+        // the picked date starts the recurrence being built
+        if self.on_recurrence.is_some() {
+            self.state.pattern.start = Some(self.state.date());
+            self.emit_recurrence(messages);
+        } else if self.close_on_select {
+            self.set_open(false, messages);
+        }
+    }
+
+    /// select the date or range of the preset, showing its first month
+    /// in the first grid when they fit, and send it
+    fn apply_preset(&mut self, preset: Preset, messages: &mut Vec<Message>) {
+        let today = self.date_today();
+        let (first, last) = preset.range(today);
+
+        let shown = self.grid_month(0);
+        let before = self.shown_months();
+
+        self.state.year = last.year();
+        self.state.month = last.month();
+        self.state.day = last.day();
+        self.state.is_empty = false;
+        self.state.range = if first == last { None } else { Some((first, last)) };
+        self.state.draft = None;
+        self.state.preview = None;
+        self.state.before_preview = None;
+        if self.state.committed.is_some() {
+            self.state.committed = Some(Some(last));
+        }
+
+        let months = (last.year() - first.year()) * 12 + last.month() as i32 - first.month() as i32;
+        match self.scrolling {
+            Some(visible) => {
+                self.state.scroll = -months.min(visible as i32 - 1) * SCROLL_UNIT as i32;
+            }
+            None => self.state.offset = months.min(self.months as i32 - 1) as u32,
+        }
+        if self.shown_months() != before {
+            self.months_changed(shown);
+        }
+
+        // the last day is sent as a picked one, then the preset's selection
+        messages.push((self.on_change)(self.format_date()));
+        if let Some(on_select) = &self.on_select {
+            messages.push(on_select(Some(last)));
+        }
+        if let Some(on_preset) = &self.on_preset {
+            messages.push(on_preset(preset.selection(today)));
+        }
+        if self.close_on_select && self.on_recurrence.is_none() {
+            self.set_open(false, messages);
+        }
+    }
+
+    /// Replace the typed text: parse it and show the month
+    /// of its date, without selecting it yet
+    fn type_draft(&mut self, draft: String) {
+        let preview = self.parser.parse(&draft, self.date_today());
+        self.state.draft = Some(draft);
+        self.show_preview(preview);
+    }
+
+    /// drop the typed text, showing the selection again
+    fn drop_draft(&mut self) {
+        self.state.draft = None;
+        self.show_preview(None);
+    }
+
+    /// show the month of the previewed date, keeping the selection;
+    /// without a preview, show again the months shown before it
+    fn show_preview(&mut self, preview: Option<NaiveDate>) {
+        let state = &mut self.state;
+        let shown = match (preview, state.preview, state.before_preview) {
+            (Some(date), _, _) => (date.year(), date.month(), date.day(), 0, 0),
+            (None, Some(_), Some(before)) => before,
+            _ => {
+                state.preview = None;
+                return
+            }
+        };
+        if state.before_preview.is_none() {
+            state.before_preview = Some((state.year, state.month, state.day, state.offset, state.scroll));
+        }
+        if preview.is_none() {
+            state.before_preview = None;
+        }
+        state.preview = preview;
+
+        let first = self.grid_month(0);
+        let before = self.shown_months();
+        let (year, month, day, offset, scroll) = shown;
+
+        self.state.year = year;
+        self.state.month = month;
+        self.state.day = day;
+        self.state.offset = offset;
+        self.state.scroll = scroll;
+        if self.shown_months() != before {
+            self.months_changed(first);
+        }
+    }
+
+    /// select the typed date, unless it is unknown or can't be picked
+    fn confirm_draft(&mut self, messages: &mut Vec<Message>) {
+        match self.state.preview {
+            Some(date) if !self.is_disabled(date) => {
+                self.select(date.year(), date.month(), date.day(), messages);
+            }
+            _ => {}
+        }
+    }
+
+    /// handle a click in the panel of presets
+    fn click_presets(&mut self, bounds: Rectangle, cursor_position: Point, messages: &mut Vec<Message>) {
+        let height = bounds.height / self.calendar_rows();
+        let row = ((cursor_position.y - bounds.y) / height) as usize;
+
+        if let Some(preset) = self.presets.get(row).copied() {
+            self.apply_preset(preset, messages);
+        }
+    }
+
+    /// empty the selection and send `None`
+    fn clear(&mut self, messages: &mut Vec<Message>) {
+        self.drop_draft();
+        self.state.is_empty = true;
+        self.state.range = None;
+        if self.state.committed.is_some() {
+            self.state.committed = Some(None);
+        }
+
+        if let Some(on_select) = &self.on_select {
+            messages.push(on_select(None));
+        }
+    }
+
+    /// the × clearing the date, at the end of the input
+    fn clear_button(&self, input: Rectangle) -> Option<Rectangle> {
+        if self.on_select.is_none() || self.state.is_empty {
+            return None
+        }
+
+        Some(Rectangle {
+            x: input.x + input.width - input.height,
+            width: input.height,
+            ..input
+        })
+    }
+
+    /// the Clear action of the footer, a cell wide, left of Next
+    fn clear_action(&self, footer: Rectangle) -> Option<Rectangle> {
+        self.on_select.as_ref()?;
+
+        let width = self.button_width(footer);
+        let next = if self.scrolling.is_some() { 0.0 } else { width };
+
+        Some(Rectangle {
+            x: footer.x + footer.width - next - width / 2.0,
+            width: width / 2.0,
+            ..footer
+        })
+    }
+
+    /// open or close the calendar, telling the host
+    fn set_open(&mut self, is_open: bool, messages: &mut Vec<Message>) {
+        if self.state.is_focused == is_open {
+            return
+        }
+        self.state.is_focused = is_open;
+
+        // an unconfirmed typed date is dropped
+        self.drop_draft();
+
+        let on_toggle = if is_open {
+            self.on_open.clone()
+        } else {
+            self.on_close.clone()
+        };
+        if let Some(on_toggle) = on_toggle {
+            messages.push(on_toggle);
+        }
+    }
+
+    /// close the calendar without picking a day
+    fn cancel(&mut self, messages: &mut Vec<Message>) {
+        if !self.state.is_focused {
+            return
+        }
+
+        if let Some(on_cancel) = self.on_cancel.clone() {
+            messages.push(on_cancel);
+        }
+        self.set_open(false, messages);
+    }
+
+    /// handle a click in the nth month grid
+    fn click_grid(
+        &mut self,
+        n: usize,
+        bounds: Rectangle,
+        cursor_position: Point,
+        messages: &mut Vec<Message>,
+    ) {
+        let (year, month) = self.grid_month(n);
+
+        if let Some((shift, (year, month), day)) =
+            self.date_at(year, month, bounds, cursor_position) {
+
+            if self.is_disabled(NaiveDate::from_ymd(year, month, day)) {
+                return
+            }
+
+            // keep the month in its grid, scrolling
+            // only when it is out of the shown months
+            let shown = self.grid_month(0);
+            let grid = (n as i32 + shift).max(0).min(self.months as i32 - 1);
+            self.state.offset = grid as u32;
+            self.select(year, month, day, messages);
+
+            if self.grid_month(0) != shown {
+                self.months_changed(shown);
+            }
+        }
+    }
+
+    /// the date of the cell under the cursor in the continuous list of months
+    fn scroll_date_at(&self, bounds: Rectangle, cursor_position: Point) -> Option<(i32, u32, u32)> {
+        let height = self.month_height(bounds);
+        let scroll = self.state.scroll as f32 / SCROLL_UNIT * height;
+
+        // the month under the cursor, counted from the month of the selected date
+        let n = ((cursor_position.y - bounds.y + scroll) / height).floor() as i32;
+        let (year, month) = add_months(self.state.year, self.state.month, n);
+        let grid = Rectangle {
+            y: bounds.y + n as f32 * height - scroll,
+            height,
+            ..bounds
+        };
+
+        self.date_at(year, month, grid, cursor_position)
+            .map(|(_, (year, month), day)| (year, month, day))
+    }
+
+    /// handle a click in the continuous list of months
+    fn click_scroll(
+        &mut self,
+        bounds: Rectangle,
+        cursor_position: Point,
+        messages: &mut Vec<Message>,
+    ) {
+        if let Some((year, month, day)) = self.scroll_date_at(bounds, cursor_position) {
+
+            if self.is_disabled(NaiveDate::from_ymd(year, month, day)) {
+                return
+            }
+
+            // the scroll is relative to the month of the selected date,
+            // move it with the selection so the list stays in place
+            let months = (year - self.state.year) * 12 + month as i32 - self.state.month as i32;
+            self.state.scroll -= months * SCROLL_UNIT as i32;
+            self.select(year, month, day, messages);
+        }
+    }
+
+    /// the day under the cursor in the open calendar
+    fn hovered_date(&self, layout: Layout<'_>, cursor_position: Point) -> Option<NaiveDate> {
+        let calendar = match layout.children().nth(1) {
+            Some(calendar) if self.state.is_focused => calendar,
+            _ => return None,
+        };
+        let (n, part) = calendar
+            .children()
+            .enumerate()
+            .find(|(_, part)| part.bounds().contains(cursor_position))?;
+
+        let (year, month, day) = if n >= self.grid_count() {
+            return None
+        } else if self.scrolling.is_some() {
+            self.scroll_date_at(part.bounds(), cursor_position)?
+        } else {
+            let (year, month) = self.grid_month(n);
+            let (_, (year, month), day) = self.date_at(year, month, part.bounds(), cursor_position)?;
+            (year, month, day)
+        };
+
+        NaiveDate::from_ymd_opt(year, month, day)
+    }
+
+    /// send the first shown month when it isn't the given one anymore
+    fn emit_month_change(&self, shown: (i32, u32), messages: &mut Vec<Message>) {
+        let (year, month) = self.shown_months().0;
+
+        if let Some(on_month_change) = &self.on_month_change {
+            if (year, month) != shown {
+                messages.push(on_month_change(year, month));
+            }
+        }
+    }
+
+    /// handle a click in the footer
+    fn click_footer(&mut self, bounds: Rectangle, cursor_position: Point, messages: &mut Vec<Message>) {
+        let width = self.button_width(bounds);
+
+        // if click the Clear action, with a date to clear
+        if let Some(clear) = self.clear_action(bounds) {
+            if clear.contains(cursor_position) {
+                if !self.state.is_empty {
+                    self.clear(messages);
+
+                    if self.close_on_select && self.on_recurrence.is_none() {
+                        self.set_open(false, messages);
+                    }
+                }
+                return
+            }
+        }
+
+        if self.scrolling.is_some() {
+            return
+        }
+
+        if cursor_position.x < bounds.x + width {
+
+            // if click pre button
+            self.previous_month();
+        } else if cursor_position.x >= bounds.x + bounds.width - width {
+
+            // if click the next button
+            self.next_month();
+        }
+    }
+
+    /// handle a click in the recurrence controls
+    fn click_pattern(
+        &mut self,
+        bounds: Rectangle,
+        cursor_position: Point,
+        messages: &mut Vec<Message>,
+    ) {
+        let (width, height) = (bounds.width / 7.0, bounds.height / 2.0);
+        let column = (((cursor_position.x - bounds.x) / width) as usize).min(6);
+        let pattern = &mut self.state.pattern;
+
+        if cursor_position.y < bounds.y + height {
+
+            // if click the frequency or interval controls
+            match column {
+                0..=3 => pattern.frequency = FREQUENCIES[column],
+                4 => pattern.interval = pattern.interval.saturating_sub(1).max(1),
+                6 => pattern.interval = (pattern.interval + 1).min(MAX_INTERVAL),
+                _ => return
+            }
+        } else if pattern.has_weekdays() {
+
+            // if click a weekday toggle
+            pattern.weekdays ^= 1 << column;
+        } else {
+            return
+        }
+
+        self.emit_recurrence(messages);
+    }
+
+    // listen all the event on the window
+    fn handle_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        messages: &mut Vec<Message>,
+    ) {
+        match event {
+
+            // listen press event
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+
+                let bounds = layout.bounds();
+                self.state.is_pressed = bounds.contains(cursor_position);
+            }
+
+            // listen released event
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+
+                let bounds = layout.bounds();
+                let is_clicked = self.state.is_pressed
+                    && bounds.contains(cursor_position);
+                self.state.is_pressed = false;
+
+                // if the click position is in the region of the widget
+                if is_clicked {
+
+                    let mut children = layout.children();
+
+                    // if click in the input area
+                    if let Some(input) = children.next() {
+                        if input.bounds().contains(cursor_position) {
+
+                            // if click the × clearing the date
+                            if let Some(clear) = self.clear_button(input.bounds()) {
+                                if clear.contains(cursor_position) {
+                                    self.clear(messages);
+                                    return
+                                }
+                            }
+
+                            // open or close the calendar
+                            let is_open = !self.state.is_focused;
+                            self.set_open(is_open, messages);
+                            return
+                        }
+                    }
+
+                    // if click in the calendar area,
+                    // find the grid, footer or controls under the cursor
+                    // a closing calendar can't be clicked
+                    let calendar = match children.next() {
+                        Some(calendar) if self.state.is_focused => calendar,
+                        _ => return,
+                    };
+                    let part = calendar
+                        .children()
+                        .enumerate()
+                        .find(|(_, part)| part.bounds().contains(cursor_position));
+
+                    let shown = self.shown_months().0;
+                    match part {
+                        Some((n, part)) if n < self.grid_count() && self.scrolling.is_some() => {
+                            self.click_scroll(part.bounds(), cursor_position, messages);
+                        }
+                        Some((n, part)) if n < self.grid_count() => {
+                            self.click_grid(n, part.bounds(), cursor_position, messages);
+                        }
+                        Some((n, part)) if n == self.grid_count() => {
+                            self.click_footer(part.bounds(), cursor_position, messages);
+                        }
+                        Some((n, part)) if n == self.panel_index() => {
+                            self.click_presets(part.bounds(), cursor_position, messages);
+                        }
+                        Some((_, part)) => {
+                            self.click_pattern(part.bounds(), cursor_position, messages);
+                        }
+                        None => {}
+                    }
+                    self.emit_month_change(shown, messages);
+                } else {
+                    
+                    // if click on the area outside of the widget
+                    self.cancel(messages);
+                }
+            }
+
+            // listen Escape, dismissing the calendar
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code: keyboard::KeyCode::Escape, .. }) => {
+                self.cancel(messages);
+            }
+
+            // listen typing while the calendar is open, the first
+            // character replacing the value, Backspace editing it
+            Event::Keyboard(keyboard::Event::CharacterReceived(c))
+                if self.state.is_focused && !c.is_control() =>
+            {
+                let first = self.shown_months().0;
+                let mut draft = self.state.draft.take().unwrap_or_default();
+                draft.push(c);
+                self.type_draft(draft);
+                self.emit_month_change(first, messages);
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code: keyboard::KeyCode::Backspace, .. })
+                if self.state.is_focused =>
+            {
+                let first = self.shown_months().0;
+                let mut draft = self.state.draft.take().unwrap_or_else(|| self.value.clone());
+                draft.pop();
+                self.type_draft(draft);
+                self.emit_month_change(first, messages);
+            }
+
+            // listen Enter, confirming the typed date
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code: keyboard::KeyCode::Enter, .. })
+                if self.state.is_focused =>
+            {
+                self.confirm_draft(messages);
+            }
+
+            // listen wheel event, scrolling the list of months,
+            // or turning the pages of months and years
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+
+                let calendar = match layout.children().nth(1) {
+                    Some(calendar) if self.state.is_focused => calendar,
+                    _ => return,
+                };
+                let part = calendar
+                    .children()
+                    .enumerate()
+                    .find(|(_, part)| part.bounds().contains(cursor_position));
+
+                let first = self.shown_months().0;
+                match part {
+                    Some((n, part)) if n < self.grid_count() && self.scrolling.is_some() => {
+                        // a line is a row of the month
+                        let height = self.month_height(part.bounds());
+                        let pixels = match delta {
+                            mouse::ScrollDelta::Lines { y, .. } => -y * height / self.grid_rows(),
+                            mouse::ScrollDelta::Pixels { y, .. } => -y,
+                        };
+                        let shown = self.shown_months();
+                        self.state.scroll += (pixels / height * SCROLL_UNIT).round() as i32;
+
+                        if self.shown_months() != shown {
+                            self.state.announcement = Some(self.shown_months());
+                        }
+                    }
+                    Some((n, part)) if n <= self.grid_count() && self.scrolling.is_none() => {
+                        let bounds = part.bounds();
+
+                        // a line turns a page, pixels are summed until they
+                        // make a row, so that a swipe doesn't fly through years
+                        let y = match delta {
+                            mouse::ScrollDelta::Lines { y, .. } => {
+                                self.state.wheel = 0;
+                                y
+                            }
+                            mouse::ScrollDelta::Pixels { y, .. } => {
+                                let row = if n < self.grid_count() {
+                                    bounds.height / self.grid_rows()
+                                } else {
+                                    bounds.height
+                                };
+                                let unit = SCROLL_UNIT as i32;
+                                self.state.wheel += (y / row * SCROLL_UNIT).round() as i32;
+
+                                // a page at most for each event, the rest of the row kept
+                                let pages = self.state.wheel / unit;
+                                self.state.wheel %= unit;
+                                pages.signum() as f32
+                            }
+                        };
+
+                        // the year is shown by the name of the month
+                        // in the grids, and by the date in the footer
+                        let is_over_year = if n < self.grid_count() {
+                            self.shows_month_names()
+                                && cursor_position.y < bounds.y + bounds.height / self.grid_rows()
+                        } else {
+                            let width = self.button_width(bounds);
+                            cursor_position.x >= bounds.x + width
+                                && cursor_position.x < bounds.x + bounds.width - width
+                        };
+
+                        // scrolling up goes back in time
+                        match (is_over_year, y > 0.0, y < 0.0) {
+                            (true, true, _) => self.previous_year(),
+                            (true, _, true) => self.next_year(),
+                            (false, true, _) => self.previous_month(),
+                            (false, _, true) => self.next_month(),
+                            _ => {}
+                        }
+                    }
+                    _ => {}
+                }
+                self.emit_month_change(first, messages);
+            }
+
+            // listen cursor moves, sending the day newly under the cursor
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+
+                let hovered = self.hovered_date(layout, cursor_position);
+
+                if hovered != self.state.hovered {
+                    self.state.hovered = hovered;
+
+                    if let (Some(on_hover), Some(date)) = (&self.on_hover, hovered) {
+                        messages.push(on_hover(date));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The state of a [`DatePicker`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct State {
+    is_pressed: bool,
+    pub is_focused: bool,
+    year: i32,
+    month: u32,
+    day: u32,
+
+    /// the grid showing the month of the selected date
+    offset: u32,
+
+    /// how far the list of months is scrolled, in thousandths
+    /// of a month, from the top of the month of the selected date
+    scroll: i32,
+    pattern: Pattern,
+
+    /// how the months and the calendar are animated,
+    /// `Animation::None` for tests and reduced motion
+    pub animation: Animation,
+
+    /// the time of the last tick
+    clock: Option<Instant>,
+
+    /// whether the calendar was shown at the last tick
+    is_shown: bool,
+
+    /// the day under the cursor, sent when it changes
+    hovered: Option<NaiveDate>,
+
+    /// no date is selected, the shown month is kept
+    is_empty: bool,
+
+    /// the first and last days of the range set by a preset,
+    /// the date being the last one
+    range: Option<(NaiveDate, NaiveDate)>,
+
+    /// in controlled mode, the date of the host when it was last
+    /// synced, or the picked one: navigating doesn't change it
+    committed: Option<Option<NaiveDate>>,
+
+    /// the text typed while the calendar is open, shown in the input
+    /// instead of the value until Enter confirms its date
+    draft: Option<String>,
+
+    /// the date of the draft, highlighted instead of the selection
+    preview: Option<NaiveDate>,
+
+    /// the selected date, grid and scroll shown before the preview,
+    /// shown again when it ends
+    before_preview: Option<(i32, u32, u32, u32, i32)>,
+
+    /// the wheel pixels not yet turning a page, in thousandths
+    /// of a row, positive upward
+    wheel: i32,
+
+    /// the months sliding or fading away, from the first shown one
+    months: Option<Transition<(i32, u32)>>,
+
+    /// the calendar opening or closing, from whether it was shown
+    popup: Option<Transition<bool>>,
+
+    /// the first and last months shown since the last announcement
+    announcement: Option<((i32, u32), (i32, u32))>,
+}
+
+/// An animation in progress, from the previous view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Transition<T> {
+
+    /// set by the first tick after the change
+    start: Option<Instant>,
+    from: T,
+}
+
+impl<T> Transition<T> {
+
+    /// how far the transition is, from 0 to 1, easing out
+    fn progress(&self, clock: Option<Instant>) -> f32 {
+        let elapsed = match (self.start, clock) {
+            (Some(start), Some(clock)) => clock.saturating_duration_since(start),
+            _ => return 0.0,
+        };
+        let t = (elapsed.as_secs_f32() / ANIMATION_DURATION.as_secs_f32()).min(1.0);
+
+        1.0 - (1.0 - t).powi(3)
+    }
+
+    /// check if the transition is over
+    fn is_over(&self, clock: Instant) -> bool {
+        matches!(self.start, Some(start) if clock.saturating_duration_since(start) >= ANIMATION_DURATION)
+    }
+}
+
+/// The recurrence being built in a [`DatePicker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pattern {
+    start: Option<NaiveDate>,
+    frequency: Frequency,
+    interval: u32,
+
+    /// one bit per weekday, Monday first
+    weekdays: u8,
+}
+
+impl Default for Pattern {
+    fn default() -> Self {
+        Pattern {
+            start: None,
+            frequency: Frequency::Weekly,
+            interval: 1,
+            weekdays: 0,
+        }
+    }
+}
+
+impl Pattern {
+
+    /// check if weekdays can be chosen for the frequency
+    fn has_weekdays(&self) -> bool {
+        self.frequency == Frequency::Daily || self.frequency == Frequency::Weekly
+    }
+
+    /// the rule described by the pattern
+    fn rule(&self) -> RecurrenceRule {
+        let mut rule = RecurrenceRule::new(self.frequency);
+        rule.interval = self.interval;
+
+        if self.has_weekdays() {
+            rule.by_day = (0..7)
+                .filter(|i| self.weekdays & (1 << i) != 0)
+                .map(|i| (None, WEEKDAYS[i]))
+                .collect();
+        }
+
+        rule
+    }
+}
+
+/// The dates selected in a [`DatePicker`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selection {
+    Single(NaiveDate),
+
+    /// from the first date to the second one, both inclusive
+    Range(NaiveDate, NaiveDate),
+    Multiple(Vec<NaiveDate>),
+}
+
+impl State {
+
+    /// Creates a new [`State`], representing an unfocus [`DatePicker`]
+    /// with current date: Year, Month, Day
+    pub fn new() -> State {
+        let local: DateTime<Local> = Local::now();
+        State {
+            is_pressed: false,
+            is_focused: false,
+            year: local.year(),
+            month: local.month(),
+            day: local.day(),
+            offset: 0,
+            scroll: 0,
+            pattern: Pattern::default(),
+            animation: Animation::None,
+            clock: None,
+            is_shown: false,
+            hovered: None,
+            is_empty: false,
+            range: None,
+            committed: None,
+            draft: None,
+            preview: None,
+            before_preview: None,
+            wheel: 0,
+            months: None,
+            popup: None,
+            announcement: None,
+        }
+    }
+
+    /// Creates a new unfocused [`State`] showing and selecting the date
+    pub fn with_date(date: NaiveDate) -> State {
+        State {
+            year: date.year(),
+            month: date.month(),
+            day: date.day(),
+            ..State::new()
+        }
+    }
+
+    /// Takes what a screen reader should announce since the last call:
+    /// the newly shown months, e.g. "July 2020"
+    pub fn take_announcement(&mut self) -> Option<String> {
+        self.announcement
+            .take()
+            .map(|(first, last)| accessibility::months_label(first, last))
+    }
+
+    /// Advances the animations to the given time.
+    /// The host calls it from a subscription while [`is_animating`]
+    ///
+    /// [`is_animating`]: #method.is_animating
+    pub fn tick(&mut self, now: Instant) {
+        self.clock = Some(now);
+
+        if self.animation == Animation::None {
+            self.is_shown = self.is_focused;
+            self.months = None;
+            self.popup = None;
+            return
+        }
+
+        // the calendar was opened or closed since the last tick
+        if self.is_focused != self.is_shown {
+            self.popup = Some(Transition {
+                start: Some(now),
+                from: self.is_shown,
+            });
+            self.is_shown = self.is_focused;
+        }
+
+        if let Some(months) = self.months.as_mut() {
+            months.start.get_or_insert(now);
+        }
+
+        if matches!(self.months, Some(months) if months.is_over(now)) {
+            self.months = None;
+        }
+        if matches!(self.popup, Some(popup) if popup.is_over(now)) {
+            self.popup = None;
+        }
+    }
+
+    /// check if an animation is in progress,
+    /// or about to start at the next tick
+    pub fn is_animating(&self) -> bool {
+        self.animation != Animation::None && (
+            self.is_focused != self.is_shown
+            || self.months.is_some()
+            || self.popup.is_some()
+        )
+    }
+
+    /// check if the calendar is shown: when focused,
+    /// and while it is closing
+    fn is_open(&self) -> bool {
+        self.is_focused || self.animation != Animation::None && (
+            self.is_shown || matches!(self.popup, Some(popup) if popup.from)
+        )
+    }
+
+    /// how much of the calendar is shown, from 0 to 1
+    fn openness(&self) -> f32 {
+        if self.animation == Animation::None {
+            return 1.0
+        }
+
+        match self.popup {
+            Some(popup) if popup.from => 1.0 - popup.progress(self.clock),
+            Some(popup) => popup.progress(self.clock),
+
+            // opened or closed, waiting for the next tick
+            None if self.is_focused != self.is_shown => if self.is_shown { 1.0 } else { 0.0 },
+            None => 1.0,
+        }
+    }
+
+    /// the recurrence built in a [`DatePicker`] with a recurrence
+    /// builder, once its start date is picked
+    pub fn recurrence(&self) -> Option<Recurrence> {
+        self.pattern.start.map(|start| Recurrence::new(start, self.pattern.rule()))
+    }
+
+    /// the selected date, the committed one in controlled mode,
+    /// or the shown one when the selection is empty
+    pub fn date(&self) -> NaiveDate {
+        match (self.committed, self.before_preview) {
+            (Some(Some(date)), _) => date,
+
+            // a typed date is only shown until it is confirmed
+            (_, Some((year, month, day, _, _))) => NaiveDate::from_ymd(year, month, day),
+            _ => NaiveDate::from_ymd(self.year, self.month, self.day),
+        }
+    }
+
+    /// the selected date, none when it was cleared
+    pub fn value(&self) -> Option<NaiveDate> {
+        if self.is_empty { None } else { Some(self.date()) }
+    }
+
+    /// show and commit the date, in the first grid,
+    /// or keep the shown month without a date
+    fn commit(&mut self, date: Option<NaiveDate>) {
+        if let Some(date) = date {
+            self.year = date.year();
+            self.month = date.month();
+            self.day = date.day();
+            self.offset = 0;
+            self.scroll = 0;
+            self.months = None;
+        }
+        self.is_empty = date.is_none();
+        self.range = None;
+        self.committed = Some(date);
+    }
+
+    /// check if the day is selected, alone or in the range,
+    /// or previewed while a date is typed
+    fn is_selected(&self, date: NaiveDate) -> bool {
+        if let Some(preview) = self.preview {
+            return preview == date
+        }
+
+        match (self.is_empty, self.range) {
+            (true, _) => false,
+            (false, Some((first, last))) => first <= date && date <= last,
+            (false, None) => self.date() == date,
+        }
+    }
+
+    /// the current selection of the [`DatePicker`], none when it was cleared
+    pub fn selection(&self) -> Option<Selection> {
+        match (self.value()?, self.range) {
+            (_, Some((first, last))) => Some(Selection::Range(first, last)),
+            (date, None) => Some(Selection::Single(date)),
+        }
+    }
+}
+
+/// the primitive with its colors made transparent by `alpha`
+fn fade(primitive: Primitive, alpha: f32) -> Primitive {
+    let fade_color = |color: Color| Color { a: color.a * alpha, ..color };
+
+    match primitive {
+        Primitive::Group { primitives } => Primitive::Group {
+            primitives: primitives.into_iter().map(|p| fade(p, alpha)).collect(),
+        },
+        Primitive::Quad { bounds, background: Background::Color(color), border_radius, border_width, border_color } => {
+            Primitive::Quad {
+                bounds,
+                background: Background::Color(fade_color(color)),
+                border_radius,
+                border_width,
+                border_color: fade_color(border_color),
+            }
+        }
+        Primitive::Text { content, bounds, color, size, font, horizontal_alignment, vertical_alignment } => {
+            Primitive::Text {
+                content,
+                bounds,
+                color: fade_color(color),
+                size,
+                font,
+                horizontal_alignment,
+                vertical_alignment,
+            }
+        }
+        Primitive::Clip { bounds, offset, content } => Primitive::Clip {
+            bounds,
+            offset,
+            content: Box::new(fade(*content, alpha)),
+        },
+        primitive => primitive,
+    }
+}
+
+impl<'a, Message, B> Widget<Message, Renderer<B>> for DatePicker<'a, Message>
+where
+    B: Backend,
+    Message: Clone,
+{
+    fn width(&self) -> Length {
+        self.width
+    }
+
+    fn height(&self) -> Length {
+        self.height
+    }
+
+    /// Layout function
+    /// Change when the focus event and unfocus event happen
+    /// return the frame of the widget:
+    /// the input, then the calendar made of the month grids,
+    /// the footer and the recurrence controls
+    fn layout(
+        &self,
+        _renderer: &Renderer<B>,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let padding = self.padding.unwrap_or(10) as f32;
+        let text_size = self.size.unwrap_or(20);
+
+        // the width of the widget, shared by the months side by side
+        // and the panel of presets
+        let across = self.grids_across() + self.panel_cells() / 7.0;
+        let width = limits
+            .width(self.width)
+            .resolve(Size::new(across * self.w as f32, 0.0))
+            .width;
+
+        // filling an unbounded parent, e.g. a scrollable row,
+        // the widget keeps its intrinsic width
+        let width = if width.is_finite() { width } else { across * self.w as f32 };
+        let month_width = width / across;
+
+        // the input is as wide as a month
+        let text_limits = limits
+            .pad(padding)
+            .width(Length::Units((month_width - 2.0 * padding).max(0.0) as u16))
+            .max_width(month_width as u32)
+            .height(Length::Units(text_size));
+
+        let mut text = layout::Node::new(text_limits.resolve(Size::ZERO));
+        text.move_to(Point::new(padding, padding));
+
+        let input = layout::Node::with_children(text.size().pad(padding), vec![text]);
+
+        // the rows of cells share the height left by the input,
+        // or are as high as the cells are wide
+        let rows = self.calendar_rows();
+        let cell = Size::new(month_width / 7.0, month_width / 7.0);
+        let available = limits
+            .height(self.height)
+            .resolve(Size::new(0.0, input.bounds().height + rows * cell.height))
+            .height - input.bounds().height;
+        let cell = match self.height {
+            Length::Shrink => cell,
+            _ if !available.is_finite() => cell,
+            _ => Size::new(cell.width, available.max(rows) / rows),
+        };
+
+        // the month grids, side by side or one under the other,
+        // or the list of months when scrolling
+        let size = cell.height;
+        let grid = Size::new(month_width, self.grid_rows() * size);
+        let (grid, months) = match self.scrolling {
+            Some(visible) => (Size::new(grid.width, visible as f32 * grid.height), 1),
+            None => (grid, self.months),
+        };
+        let mut parts: Vec<layout::Node> = (0..months)
+            .map(|n| {
+                let mut node = layout::Node::new(grid);
+                node.move_to(match self.arrangement {
+                    Arrangement::Row => Point::new(n as f32 * grid.width, 0.0),
+                    Arrangement::Column => Point::new(0.0, n as f32 * grid.height),
+                });
+                node
+            })
+            .collect();
+        let grids = match self.arrangement {
+            Arrangement::Row => Size::new(months as f32 * grid.width, grid.height),
+            Arrangement::Column => Size::new(grid.width, months as f32 * grid.height),
+        };
+
+        // the footer under the grids: Pre, selected date, Next
+        let mut footer = layout::Node::new(Size::new(grids.width, size));
+        footer.move_to(Point::new(0.0, grids.height));
+        parts.push(footer);
+
+        // the recurrence controls take two more rows
+        let mut height = grids.height + size;
+        if self.shows_pattern() {
+            let mut pattern = layout::Node::new(Size::new(7.0 * cell.width, 2.0 * size));
+            pattern.move_to(Point::new(0.0, height));
+            parts.push(pattern);
+            height += 2.0 * size;
+        }
+
+        // the presets on the right, as high as the calendar
+        let panel = self.panel_cells() * cell.width;
+        if panel > 0.0 {
+            let mut presets = layout::Node::new(Size::new(panel, height));
+            presets.move_to(Point::new(grids.width, 0.0));
+            parts.push(presets);
+        }
+
+        let mut calendar = layout::Node::with_children(
+            Size::new(grids.width + panel, height),
+            parts
+        );
+        calendar.move_to(Point::new(0.0, input.bounds().height));
+
+        // the calendar stays while it is closing
+        let is_open = self.state.is_open();
+        layout::Node::with_children(Size::new(
+            if is_open {
+                calendar.bounds().width.max(month_width)
+            } else {
+                month_width
+            },
+            if is_open {
+                input.bounds().height + calendar.bounds().height
+            } else {
+                input.bounds().height
+            }
+        ), if is_open {
+            vec![input, calendar]
+        } else {
+            vec![input]
+        })
+    }
+
+    /// through the hash values of several properties
+    /// to decide if need to relayout the most bottom view
+    fn hash_layout(&self, state: &mut Hasher) {
+        use std::hash::Hash;
+        struct Marker;
+        std::any::TypeId::of::<Marker>().hash(state);
+
+        self.w.hash(state);
+        self.width.hash(state);
+        self.height.hash(state);
+        self.padding.hash(state);
+        self.presets.hash(state);
+        self.size.hash(state);
+        self.months.hash(state);
+        self.arrangement.hash(state);
+        self.scrolling.hash(state);
+        self.state.is_focused.hash(state);
+        self.state.is_open().hash(state);
+        self.shows_pattern().hash(state);
+    }
+
+    /// According the bound of the layout node,
+    /// Draw the view
+    fn draw(
+        &self,
+        _renderer: &mut Renderer<B>,
+        _defaults: &Defaults,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+    ) -> (Primitive, mouse::Interaction) {
+
+        let is_mouse_over = layout.bounds().contains(_cursor_position);
+        let mut v = Vec::new();
+
+        // Draw the most bottom view: Background
+        {
+            let bound = layout.bounds();
+            v.push(Primitive::Quad {
+                bounds: bound,
+                background: Background::Color(Color::WHITE),
+                border_radius: 0,
+                border_width: 0,
+                border_color: Color::TRANSPARENT,
+            });
+        }
+
+        for (i, child) in layout.children().enumerate() {
+
+            let bound = child.bounds();
+            if i == 0 {
+                // Draw the input view and text view 
+                v.push(Primitive::Quad {
+                    bounds: bound,
+                    background: Background::Color(Color::WHITE),
+                    border_radius: 5,
+                    border_width: 1,
+                    border_color: BORDER_COLOR,
+                });
+
+                // the text being typed replaces the value
+                let text = self.state.draft.as_ref().unwrap_or(&self.value);
+                for chi in child.children() {
+                    v.push(Primitive::Text {
+                        content: if text.is_empty() {
+                            self.placeholder.clone()
+                        } else {
+                            text.clone()
+                        },
+                        color: if text.is_empty() {
+                            SECOND_TEXT_COLOR
+                        } else {
+                            FIRST_TEXT_COLOR 
+                        },
+                        font: self.font,
+                        bounds: Rectangle {
+                            y: chi.bounds().center_y(),
+                            width: f32::INFINITY,
+                            ..chi.bounds()
+                        },
+                        size: self.size.unwrap_or(20) as f32,
+                        horizontal_alignment: HorizontalAlignment::Left,
+                        vertical_alignment: VerticalAlignment::Center,
+                    });
+                }
+
+                // Draw the × clearing the date
+                if let Some(b) = self.clear_button(bound) {
+                    v.push(Primitive::Text {
+                        content: String::from("×"),
+                        bounds: Rectangle { x: b.center_x(), y: b.center_y(), ..b },
+                        color: if b.contains(_cursor_position) {
+                            FIRST_TEXT_COLOR
+                        } else {
+                            SECOND_TEXT_COLOR
+                        },
+                        font: self.font,
+                        size: self.size.unwrap_or(20) as f32,
+                        horizontal_alignment: HorizontalAlignment::Center,
+                        vertical_alignment: VerticalAlignment::Center,
+                    });
+                }
+            } else if i == 1 {
+
+                // Draw the calendar view: a background,
+                // then the month grids, the footer and the recurrence controls,
+                // apart from the rest while it opens or closes
+                let mut rest = std::mem::take(&mut v);
+                v.push(Primitive::Quad {
+                    bounds: bound,
+                    background: Background::Color(Color::WHITE),
+                    border_radius: 0,
+                    border_width: 1,
+                    border_color: BORDER_COLOR,
+                });
+
+                let mut hovered_holiday = None;
+                for (n, part) in child.children().enumerate() {
+                    if n < self.grid_count() && self.scrolling.is_some() {
+                        self.draw_scroll(&mut v, part.bounds(),
+                            _cursor_position, &mut hovered_holiday);
+                    } else if let (true, Some(transition)) =
+                        (n < self.grid_count(), self.state.months) {
+                        self.draw_transition(&mut v, part.bounds(), n, transition);
+                    } else if n < self.grid_count() {
+                        let (year, month) = self.grid_month(n);
+                        self.draw_grid(&mut v, part.bounds(), year, month,
+                            _cursor_position, &mut hovered_holiday);
+                    } else if n == self.grid_count() {
+                        self.draw_footer(&mut v, part.bounds());
+                    } else if n == self.panel_index() {
+                        self.draw_presets(&mut v, part.bounds());
+                    } else {
+                        self.draw_pattern(&mut v, part.bounds());
+                    }
+                }
+
+                // Draw the name of the hovered holiday as a tooltip
+                // above its cell, on top of everything else
+                if let Some((cell, name)) = hovered_holiday {
+                    let size = cell.width.min(cell.height);
+                    let font1 = self.header_text_size(size);
+                    let width = (name.len() as f32 * font1 * 0.6 + font1)
+                        .min(bound.width);
+                    let b = Rectangle {
+                        x: cell.center_x() - width / 2.0,
+                        y: cell.y - size / 2.0,
+                        width,
+                        height: size / 2.0,
+                    };
+                    let b = Rectangle {
+                        x: b.x.max(bound.x).min(bound.x + bound.width - width),
+                        ..b
+                    };
+
+                    v.push(Primitive::Quad {
+                        bounds: b,
+                        background: Background::Color(Color::WHITE),
+                        border_radius: 3,
+                        border_width: 1,
+                        border_color: BORDER_COLOR,
+                    });
+
+                    let x = b.center_x();
+                    let y = b.center_y();
+
+                    v.push(Primitive::Text {
+                        content: name,
+                        bounds: Rectangle { x, y, ..b },
+                        color: HOLIDAY_TEXT_COLOR,
+                        size: font1,
+                        font: self.header_font,
+                        horizontal_alignment: HorizontalAlignment::Center,
+                        vertical_alignment: VerticalAlignment::Center,
+                    });
+                }
+
+                // the calendar rolls down from the input, or fades in
+                let openness = self.state.openness();
+                let calendar = Primitive::Group { primitives: v };
+                rest.push(match self.state.animation {
+                    _ if openness >= 1.0 => calendar,
+                    Animation::Slide => Primitive::Clip {
+                        bounds: bound,
+                        offset: Vector::new(0, ((1.0 - openness) * bound.height) as u32),
+                        content: Box::new(calendar),
+                    },
+                    _ => fade(calendar, openness),
+                });
+                v = rest;
+            }
+        }
+        
+        (
+            Primitive::Group{
+                primitives: v
+            },
+            if is_mouse_over {
+                mouse::Interaction::Pointer
+            } else {
+                mouse::Interaction::default()
+            },
+        )
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        messages: &mut Vec<Message>,
+        _renderer: &Renderer<B>,
+        _clipboard: Option<&dyn Clipboard>,
+    ) {
+        let was_animating = self.state.is_animating();
+        self.handle_event(event, layout, cursor_position, messages);
+
+        // the host reads its subscriptions again after a message only
+        if !was_animating && self.state.is_animating() {
+            if let Some(on_animation_start) = &self.on_animation_start {
+                messages.push(on_animation_start.clone());
+            }
+        }
+    }}
+
+impl<'a, Message, B> Into<Element<'a, Message, Renderer<B>>> for DatePicker<'a, Message>
+where
+    B: Backend,
+    Message: 'a + Clone,
+{
+    fn into(self) -> Element<'a, Message, Renderer<B>> {
+        Element::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headless::Headless;
+    use crate::holiday::{Rule, RuleSet};
+    use crate::simulation::Simulation;
+
+    fn wheel(delta: mouse::ScrollDelta) -> Event {
+        Event::Mouse(mouse::Event::WheelScrolled { delta })
+    }
+
+    fn month(state: &State) -> (i32, u32) {
+        (state.date().year(), state.date().month())
+    }
+
+    #[test]
+    fn wheel_pixels_turn_a_page_a_row() {
+        let mut state = State::with_date(NaiveDate::from_ymd(2020, 6, 5));
+        state.is_focused = true;
+        let mut simulation = Simulation::new(state, Size::new(1000.0, 1000.0), |state| {
+            DatePicker::new(350, state, "", "2020-06-05", |value| value)
+        });
+        let grid = simulation.position(Target::Day(15)).unwrap();
+        let swipe = wheel(mouse::ScrollDelta::Pixels { x: 0.0, y: -10.0 });
+
+        // rows are 50 pixels high: four events don't make one
+        simulation.events(&vec![swipe.clone(); 4], grid);
+        assert_eq!(month(simulation.state()), (2020, 6));
+
+        simulation.events(&[swipe], grid);
+        assert_eq!(month(simulation.state()), (2020, 7));
+
+        // a long swipe turns a page at most for each event
+        simulation.events(&[wheel(mouse::ScrollDelta::Pixels { x: 0.0, y: -400.0 })], grid);
+        assert_eq!(month(simulation.state()), (2020, 8));
+
+        // back up, and lines turn a page each
+        simulation.events(&vec![wheel(mouse::ScrollDelta::Pixels { x: 0.0, y: 10.0 }); 5], grid);
+        assert_eq!(month(simulation.state()), (2020, 7));
+        simulation.events(&[wheel(mouse::ScrollDelta::Lines { x: 0.0, y: 1.0 })], grid);
+        assert_eq!(month(simulation.state()), (2020, 6));
+    }
+
+    #[test]
+    fn focused_day_description() {
+        let holidays = RuleSet::new().rule("Picnic Day", Rule::Fixed { month: 6, day: 5 });
+        let mut state = State::with_date(NaiveDate::from_ymd(2020, 6, 5));
+        state.is_focused = true;
+
+        let picker = DatePicker::new(350, &mut state, "", "2020-06-05", |value: String| value)
+            .today(NaiveDate::from_ymd(2021, 1, 1))
+            .holidays(&holidays);
+        let description = picker.description();
+
+        let focused = description.focused().unwrap();
+        assert_eq!(focused.role, Role::GridCell);
+        assert_eq!(focused.label, "Friday, 5 June 2020, selected, holiday");
+        assert_eq!(focused.description.as_deref(), Some("Picnic Day"));
+        assert!(focused.is_selected);
+
+        let grid = description.find(&|node| node.role == Role::Grid).unwrap();
+        assert_eq!(grid.label, "June 2020");
+
+        // a row of weekdays, then six weeks
+        assert_eq!(grid.children.len(), 7);
+        assert_eq!(grid.children[0].children[0].label, "Monday");
+        assert!(grid.find_by_label(Role::GridCell, "Friday, 5 June 2020, selected, holiday").is_some());
+    }
+
+    /// how many nodes of the tree match
+    fn count(node: &Node, matches: &dyn Fn(&Node) -> bool) -> usize {
+        node.children.iter().map(|child| count(child, matches)).sum::<usize>() + matches(node) as usize
+    }
+
+    #[test]
+    fn range_has_one_focused_day() {
+        fn build(state: &mut State) -> DatePicker<'_, String> {
+            DatePicker::new(350, state, "", "", |value| value)
+                .today(NaiveDate::from_ymd(2020, 6, 12))
+                .presets(vec![Preset::LastDays(7)], |_| String::new())
+                .close_on_select(false)
+        }
+        let mut state = State::with_date(NaiveDate::from_ymd(2020, 6, 5));
+        state.is_focused = true;
+        let mut simulation = Simulation::new(state, Size::new(1000.0, 1000.0), build);
+        simulation.click(Target::Preset(Preset::LastDays(7)));
+
+        let mut state = simulation.state().clone();
+        let description = build(&mut state).description();
+
+        assert_eq!(count(&description, &|node| node.is_selected), 7);
+        assert_eq!(count(&description, &|node| node.is_focused), 1);
+        assert_eq!(description.focused().unwrap().label, "Friday, 12 June 2020, selected, today");
+    }
+
+    #[test]
+    fn days_of_other_months_are_not_selected() {
+        let mut state = State::with_date(NaiveDate::from_ymd(2020, 6, 30));
+        state.is_focused = true;
+
+        // July starts on Wednesday, after 29 and 30 June
+        let description = DatePicker::new(350, &mut state, "", "", |value: String| value)
+            .months(2)
+            .today(NaiveDate::from_ymd(2021, 1, 1))
+            .description();
+
+        assert_eq!(count(&description, &|node| node.is_selected), 1);
+        assert_eq!(count(&description, &|node| node.is_focused), 1);
+        assert_eq!(count(&description, &|node| node.label.starts_with("Tuesday, 30 June 2020")), 2);
+    }
+
+    #[test]
+    fn unfocused_description() {
+        let mut state = State::with_date(NaiveDate::from_ymd(2020, 6, 5));
+        let picker = DatePicker::new(350, &mut state, "Choose a date...", "", |value: String| value);
+        let description = picker.description();
+
+        assert_eq!(description.children.len(), 1);
+        assert_eq!(description.children[0].role, Role::TextInput);
+        assert_eq!(description.children[0].label, "Choose a date...");
+        assert!(description.focused().is_none());
+    }
+
+    #[test]
+    fn announcement_after_next() {
+        let mut simulation = Simulation::new(
+            State::with_date(NaiveDate::from_ymd(2020, 6, 5)),
+            Size::new(1000.0, 1000.0),
+            |state| DatePicker::new(350, state, "", "2020-06-05", |value| value),
+        );
+        simulation.click(Target::Input);
+        assert_eq!(simulation.state_mut().take_announcement(), None);
+
+        simulation.click(Target::Next);
+        assert_eq!(simulation.state_mut().take_announcement().as_deref(), Some("July 2020"));
+
+        // announced once
+        assert_eq!(simulation.state_mut().take_announcement(), None);
+    }
+
+    /// the size of the unfocused picker laid out within the limits
+    fn laid_out(width: Length, max: Size) -> Size {
+        let mut state = State::with_date(NaiveDate::from_ymd(2020, 6, 5));
+        let picker = DatePicker::new(350, &mut state, "", "2020-06-05", |value| value).width(width);
+
+        Widget::<String, Renderer<Headless>>::layout(
+            &picker,
+            &Renderer::new(Headless),
+            &layout::Limits::new(Size::ZERO, max),
+        )
+        .size()
+    }
+
+    #[test]
+    fn fill_width() {
+        assert_eq!(laid_out(Length::Fill, Size::new(600.0, 1000.0)).width, 600.0);
+        assert_eq!(laid_out(Length::Shrink, Size::new(600.0, 1000.0)).width, 350.0);
+
+        // unbounded, as within a scrollable row: the intrinsic width
+        assert_eq!(laid_out(Length::Fill, Size::new(f32::INFINITY, f32::INFINITY)).width, 350.0);
+    }
+}
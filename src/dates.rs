@@ -0,0 +1,133 @@
+///
+/// Date arithmetic shared by the views, holidays and recurrences
+///
+use chrono::prelude::*;
+use chrono::Duration;
+
+/// the weekdays, Monday first
+pub const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu,
+    Weekday::Fri, Weekday::Sat, Weekday::Sun,
+];
+
+/// the last day of the given month
+pub fn last_day_of_month(year: i32, month: u32) -> Option<NaiveDate> {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+
+    NaiveDate::from_ymd_opt(next_year, next_month, 1).map(|date| date.pred())
+}
+
+/// how many days the month has
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    last_day_of_month(year, month).map_or(0, |date| date.day())
+}
+
+/// the nth weekday of a month, counted from the end when n is negative
+pub fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: i32) -> Option<NaiveDate> {
+    if n > 0 {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let offset = (weekday.num_days_from_monday() + 7
+            - first.weekday().num_days_from_monday()) % 7;
+        let day = 1 + offset as i32 + 7 * (n - 1);
+
+        NaiveDate::from_ymd_opt(year, month, day as u32)
+    } else if n < 0 {
+        let last = last_day_of_month(year, month)?;
+        let offset = (last.weekday().num_days_from_monday() + 7
+            - weekday.num_days_from_monday()) % 7;
+        let day = last.day() as i32 - offset as i32 - 7 * (-n - 1);
+
+        if day < 1 {
+            None
+        } else {
+            NaiveDate::from_ymd_opt(year, month, day as u32)
+        }
+    } else {
+        None
+    }
+}
+
+/// the nth weekday of a year, counted from the end when n is negative
+pub fn nth_weekday_of_year(year: i32, weekday: Weekday, n: i32) -> Option<NaiveDate> {
+    let date = if n > 0 {
+        let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+        let offset = (weekday.num_days_from_monday() + 7
+            - first.weekday().num_days_from_monday()) % 7;
+
+        first.checked_add_signed(Duration::days(offset as i64 + 7 * (n as i64 - 1)))?
+    } else if n < 0 {
+        let last = NaiveDate::from_ymd_opt(year, 12, 31)?;
+        let offset = (last.weekday().num_days_from_monday() + 7
+            - weekday.num_days_from_monday()) % 7;
+
+        last.checked_sub_signed(Duration::days(offset as i64 + 7 * (-n as i64 - 1)))?
+    } else {
+        return None
+    };
+
+    if date.year() == year {
+        Some(date)
+    } else {
+        None
+    }
+}
+
+/// the month `months` months after the given one
+pub fn add_months(year: i32, month: u32, months: i32) -> (i32, u32) {
+    let index = year * 12 + month as i32 - 1 + months;
+    (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+}
+
+/// the Monday on or before the date, where its week starts
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_of_months() {
+        assert_eq!(days_in_month(2020, 1), 31);
+        assert_eq!(days_in_month(2020, 4), 30);
+        assert_eq!(days_in_month(2020, 12), 31);
+
+        // leap years: every 4 years, but not every 100 unless every 400
+        assert_eq!(days_in_month(2020, 2), 29);
+        assert_eq!(days_in_month(2021, 2), 28);
+        assert_eq!(days_in_month(2100, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+    }
+
+    #[test]
+    fn months_added() {
+        assert_eq!(add_months(2020, 12, 1), (2021, 1));
+        assert_eq!(add_months(2020, 1, -1), (2019, 12));
+        assert_eq!(add_months(2020, 6, -18), (2018, 12));
+        assert_eq!(add_months(2020, 6, 0), (2020, 6));
+    }
+
+    #[test]
+    fn weeks_start_on_monday() {
+        assert_eq!(week_start(NaiveDate::from_ymd(2020, 6, 5)), NaiveDate::from_ymd(2020, 6, 1));
+        assert_eq!(week_start(NaiveDate::from_ymd(2020, 6, 1)), NaiveDate::from_ymd(2020, 6, 1));
+        assert_eq!(week_start(NaiveDate::from_ymd(2021, 1, 3)), NaiveDate::from_ymd(2020, 12, 28));
+    }
+
+    #[test]
+    fn weekdays_of_a_year() {
+        assert_eq!(nth_weekday_of_year(2021, Weekday::Mon, 1), Some(NaiveDate::from_ymd(2021, 1, 4)));
+        assert_eq!(nth_weekday_of_year(2021, Weekday::Mon, 20), Some(NaiveDate::from_ymd(2021, 5, 17)));
+        assert_eq!(nth_weekday_of_year(2021, Weekday::Fri, -1), Some(NaiveDate::from_ymd(2021, 12, 31)));
+        assert_eq!(nth_weekday_of_year(2021, Weekday::Fri, -2), Some(NaiveDate::from_ymd(2021, 12, 24)));
+
+        // 2021 has 52 Mondays
+        assert_eq!(nth_weekday_of_year(2021, Weekday::Mon, 53), None);
+        assert_eq!(nth_weekday_of_year(2021, Weekday::Mon, 0), None);
+    }
+}
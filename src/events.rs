@@ -0,0 +1,100 @@
+///
+/// Calendar events shown as markers in the DatePicker
+///
+use chrono::prelude::*;
+use chrono::Duration;
+
+use crate::recurrence::{Recurrence, RecurrenceRule};
+
+/// An event in a calendar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEvent {
+    pub summary: String,
+    pub description: Option<String>,
+    pub start: NaiveDateTime,
+
+    /// the end of the event, exclusive
+    pub end: NaiveDateTime,
+    pub all_day: bool,
+
+    /// the rule repeating the event, starting on its first day
+    pub recurrence: Option<Recurrence>,
+}
+
+impl CalendarEvent {
+
+    /// create an all-day event from `start` to `end`, both inclusive
+    pub fn all_day(summary: &str, start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            summary: String::from(summary),
+            description: None,
+            start: start.and_hms(0, 0, 0),
+            end: end.succ().and_hms(0, 0, 0),
+            all_day: true,
+            recurrence: None,
+        }
+    }
+
+    /// create an event from `start` to `end`
+    pub fn timed(summary: &str, start: NaiveDateTime, end: NaiveDateTime) -> Self {
+        Self {
+            summary: String::from(summary),
+            description: None,
+            start,
+            end,
+            all_day: false,
+            recurrence: None,
+        }
+    }
+
+    /// repeat the event with the rule
+    pub fn repeat(mut self, rule: RecurrenceRule) -> Self {
+        self.recurrence = Some(Recurrence::new(self.first_day(), rule));
+        self
+    }
+
+    /// the first day the event takes place
+    pub fn first_day(&self) -> NaiveDate {
+        self.start.date()
+    }
+
+    /// the last day the event takes place.
+    /// an event ending at midnight doesn't take place that day
+    pub fn last_day(&self) -> NaiveDate {
+        if self.end > self.start && self.end.time() == NaiveTime::from_hms(0, 0, 0) {
+            (self.end - Duration::days(1)).date()
+        } else {
+            self.end.date().max(self.first_day())
+        }
+    }
+
+    /// check if the event, or one of its repetitions, takes place on the date
+    pub fn occurs_on(&self, date: NaiveDate) -> bool {
+        match &self.recurrence {
+            Some(recurrence) => {
+                let length = self.last_day() - self.first_day();
+                !recurrence.occurrences_between(date - length, date).is_empty()
+            }
+            None => self.first_day() <= date && date <= self.last_day(),
+        }
+    }
+}
+
+/// A source of events for the [`DatePicker`]
+pub trait EventSource {
+
+    /// the events which take place on the date
+    fn events_on(&self, date: NaiveDate) -> Vec<&CalendarEvent>;
+}
+
+impl EventSource for [CalendarEvent] {
+    fn events_on(&self, date: NaiveDate) -> Vec<&CalendarEvent> {
+        self.iter().filter(|event| event.occurs_on(date)).collect()
+    }
+}
+
+impl EventSource for Vec<CalendarEvent> {
+    fn events_on(&self, date: NaiveDate) -> Vec<&CalendarEvent> {
+        self.as_slice().events_on(date)
+    }
+}
@@ -0,0 +1,37 @@
+///
+/// Widgets laid out and drawn without a window nor a GPU,
+/// for the SVG export and the snapshots
+///
+use iced_graphics::{Backend, Primitive, Renderer};
+use iced_native::{layout, Cache, Element, Point, Size, UserInterface};
+
+/// A backend drawing nothing: the widgets only build primitives
+pub struct Headless;
+
+impl Backend for Headless {}
+
+/// Lays out the widget within the size and draws it with the cursor away,
+/// giving its primitives and the size of its layout
+pub fn draw<'a, Message, W>(widget: W, size: Size) -> (Primitive, Size)
+where
+    W: Into<Element<'a, Message, Renderer<Headless>>>,
+{
+    // nothing is hovered
+    draw_at(widget, size, Point::new(-1.0, -1.0))
+}
+
+/// Lays out the widget within the size and draws it with the cursor
+/// at the position, giving its primitives and the size of its layout
+pub fn draw_at<'a, Message, W>(widget: W, size: Size, cursor_position: Point) -> (Primitive, Size)
+where
+    W: Into<Element<'a, Message, Renderer<Headless>>>,
+{
+    let mut renderer = Renderer::new(Headless);
+    let element = widget.into();
+
+    let node = element.layout(&renderer, &layout::Limits::new(Size::ZERO, size));
+    let mut user_interface = UserInterface::build(element, size, Cache::new(), &mut renderer);
+    let (primitive, _) = user_interface.draw(&mut renderer, cursor_position);
+
+    (primitive, node.size())
+}
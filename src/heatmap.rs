@@ -0,0 +1,491 @@
+///
+/// Year heatmap Widget: one column per week, one colored cell per day
+///
+use std::collections::HashMap;
+
+use iced_graphics::{Backend, Defaults, Primitive, Renderer};
+use iced_native::{
+    layout, mouse, Background, Color, Element, Hasher, Layout, Length,
+    Point, Size, Widget, Event, Clipboard, Rectangle, Font, HorizontalAlignment,
+    VerticalAlignment,
+};
+
+use chrono::prelude::*;
+use chrono::Duration;
+
+use crate::dates::week_start;
+use crate::style::BORDER_COLOR;
+
+const WEEK_DAYS: [&str; 7] = ["Mon", "", "Wed", "", "Fri", "", ""];
+
+const TEXT_COLOR: Color = Color{r: 87.0/255.0, g: 96.0/255.0, b: 106.0/255.0, a: 1.0};
+
+/// Maps the values of the days to colors: the empty color for zero
+/// and below, then evenly spread steps up to the maximum value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorScale {
+    empty: Color,
+    steps: Vec<Color>,
+
+    /// the value of the last step, the largest value of the year if none
+    max: Option<f32>,
+}
+
+impl Default for ColorScale {
+
+    /// the greens of GitHub's contribution graph
+    fn default() -> Self {
+        Self::new(
+            Color{r: 235.0/255.0, g: 237.0/255.0, b: 240.0/255.0, a: 1.0},
+            vec![
+                Color{r: 155.0/255.0, g: 233.0/255.0, b: 168.0/255.0, a: 1.0},
+                Color{r: 64.0/255.0, g: 196.0/255.0, b: 99.0/255.0, a: 1.0},
+                Color{r: 48.0/255.0, g: 161.0/255.0, b: 78.0/255.0, a: 1.0},
+                Color{r: 33.0/255.0, g: 110.0/255.0, b: 57.0/255.0, a: 1.0},
+            ],
+        )
+    }
+}
+
+impl ColorScale {
+
+    /// create a [`ColorScale`] from the color of the days
+    /// without value and the colors of the steps, lowest first
+    pub fn new(empty: Color, steps: Vec<Color>) -> Self {
+        Self {
+            empty,
+            steps,
+            max: None,
+        }
+    }
+
+    /// Sets the value of the last step, larger values get its color too
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// the color of the value, given the largest value of the year
+    pub fn color(&self, value: f32, largest: f32) -> Color {
+        let max = self.max.unwrap_or(largest);
+
+        if value <= 0.0 || max <= 0.0 || self.steps.is_empty() {
+            return self.empty
+        }
+
+        let step = (value / max * self.steps.len() as f32).ceil() as usize;
+        self.steps[step.max(1).min(self.steps.len()) - 1]
+    }
+}
+
+/// A year of days colored by their value, weeks as columns
+pub struct Heatmap<'a, Message> {
+    state: &'a mut State,
+    year: i32,
+    values: &'a HashMap<NaiveDate, f32>,
+    on_select: Box<dyn Fn(NaiveDate) -> Message>,
+    selected: Option<NaiveDate>,
+    cell_size: u16,
+    scale: ColorScale,
+}
+
+impl<'a, Message> Heatmap<'a, Message> {
+
+    /// create a new [`Heatmap`] of the year, sending the clicked days
+    pub fn new<F>(
+        year: i32,
+        state: &'a mut State,
+        values: &'a HashMap<NaiveDate, f32>,
+        on_select: F,
+    ) -> Self
+    where
+        F: 'static + Fn(NaiveDate) -> Message,
+    {
+        Self {
+            state,
+            year,
+            values,
+            on_select: Box::new(on_select),
+            selected: None,
+            cell_size: 12,
+            scale: ColorScale::default(),
+        }
+    }
+
+    /// Sets the day drawn with a border
+    pub fn selected(mut self, date: Option<NaiveDate>) -> Self {
+        self.selected = date;
+        self
+    }
+
+    /// Sets the side of a day cell
+    pub fn cell_size(mut self, size: u16) -> Self {
+        self.cell_size = size.max(1);
+        self
+    }
+
+    /// Sets the [`ColorScale`] of the values
+    pub fn scale(mut self, scale: ColorScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// the Monday of the first column, on or before 1 January
+    fn first_day(&self) -> NaiveDate {
+        week_start(NaiveDate::from_ymd(self.year, 1, 1))
+    }
+
+    /// how many weeks the year spans
+    fn weeks(&self) -> i64 {
+        let last = NaiveDate::from_ymd(self.year, 12, 31);
+        (last - self.first_day()).num_days() / 7 + 1
+    }
+
+    /// the room for the weekday names on the left
+    /// and the month names on the top
+    fn margins(&self) -> (f32, f32) {
+        let size = self.cell_size as f32;
+        (3.0 * size, 1.5 * size)
+    }
+
+    /// the bounds of the cell of the date
+    fn cell_bounds(&self, bounds: Rectangle, date: NaiveDate) -> Rectangle {
+        let size = self.cell_size as f32;
+        let (left, top) = self.margins();
+        let days = (date - self.first_day()).num_days();
+
+        Rectangle {
+            x: bounds.x + left + (days / 7) as f32 * size,
+            y: bounds.y + top + (days % 7) as f32 * size,
+            width: size,
+            height: size,
+        }
+    }
+
+    /// the day of the year under the cursor
+    fn date_at(&self, bounds: Rectangle, cursor_position: Point) -> Option<NaiveDate> {
+        let size = self.cell_size as f32;
+        let (left, top) = self.margins();
+        let x = cursor_position.x - bounds.x - left;
+        let y = cursor_position.y - bounds.y - top;
+
+        if x < 0.0 || y < 0.0 || y >= 7.0 * size {
+            return None
+        }
+
+        let week = (x / size) as i64;
+        let weekday = (y / size) as i64;
+        let date = self.first_day() + Duration::days(week * 7 + weekday);
+
+        if date.year() == self.year {
+            Some(date)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, Message, B> Widget<Message, Renderer<B>> for Heatmap<'a, Message>
+where
+    B: Backend,
+{
+    fn width(&self) -> Length {
+        Length::Shrink
+    }
+
+    fn height(&self) -> Length {
+        Length::Shrink
+    }
+
+    /// the weekday names, then a column of seven days per week,
+    /// under the month names
+    fn layout(
+        &self,
+        _renderer: &Renderer<B>,
+        _limits: &layout::Limits,
+    ) -> layout::Node {
+        let size = self.cell_size as f32;
+        let (left, top) = self.margins();
+
+        layout::Node::new(Size::new(
+            left + self.weeks() as f32 * size,
+            top + 7.0 * size,
+        ))
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        use std::hash::Hash;
+        struct Marker;
+        std::any::TypeId::of::<Marker>().hash(state);
+
+        self.year.hash(state);
+        self.cell_size.hash(state);
+    }
+
+    fn draw(
+        &self,
+        _renderer: &mut Renderer<B>,
+        _defaults: &Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+    ) -> (Primitive, mouse::Interaction) {
+        let bounds = layout.bounds();
+        let size = self.cell_size as f32;
+        let font = 0.9 * size;
+        let (left, top) = self.margins();
+        let mut v = Vec::new();
+
+        // Draw the names of the months above their first week
+        for month in 1..=12 {
+            let first = NaiveDate::from_ymd(self.year, month, 1);
+            let b = self.cell_bounds(bounds, first);
+
+            v.push(Primitive::Text {
+                content: first.format("%b").to_string(),
+                bounds: Rectangle {
+                    y: bounds.y + top / 2.0,
+                    width: 4.0 * size,
+                    ..b
+                },
+                color: TEXT_COLOR,
+                size: font,
+                font: Font::default(),
+                horizontal_alignment: HorizontalAlignment::Left,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+        }
+
+        // Draw the names of every other weekday
+        for (weekday, name) in WEEK_DAYS.iter().enumerate().filter(|(_, name)| !name.is_empty()) {
+            v.push(Primitive::Text {
+                content: name.to_string(),
+                bounds: Rectangle {
+                    x: bounds.x,
+                    y: bounds.y + top + (weekday as f32 + 0.5) * size,
+                    width: left,
+                    height: size,
+                },
+                color: TEXT_COLOR,
+                size: font,
+                font: Font::default(),
+                horizontal_alignment: HorizontalAlignment::Left,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+        }
+
+        // Draw the days, colored by their value
+        let largest = self.values
+            .iter()
+            .filter(|(date, _)| date.year() == self.year)
+            .map(|(_, value)| *value)
+            .fold(0.0, f32::max);
+
+        let mut date = NaiveDate::from_ymd(self.year, 1, 1);
+        while date.year() == self.year {
+            let b = self.cell_bounds(bounds, date);
+            let value = self.values.get(&date).copied().unwrap_or(0.0);
+            let is_outlined = self.selected == Some(date) || b.contains(cursor_position);
+
+            // a gap between the cells
+            let gap = (size / 6.0).max(1.0);
+            v.push(Primitive::Quad {
+                bounds: Rectangle {
+                    x: b.x + gap / 2.0,
+                    y: b.y + gap / 2.0,
+                    width: size - gap,
+                    height: size - gap,
+                },
+                background: Background::Color(self.scale.color(value, largest)),
+                border_radius: (size / 6.0) as u16,
+                border_width: if is_outlined { 1 } else { 0 },
+                border_color: BORDER_COLOR,
+            });
+
+            date = date.succ();
+        }
+
+        let is_over_day = self.date_at(bounds, cursor_position).is_some();
+
+        (
+            Primitive::Group {
+                primitives: v
+            },
+            if is_over_day {
+                mouse::Interaction::Pointer
+            } else {
+                mouse::Interaction::default()
+            },
+        )
+    }
+
+    // send the day under the cursor when clicked
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        messages: &mut Vec<Message>,
+        _renderer: &Renderer<B>,
+        _clipboard: Option<&dyn Clipboard>,
+    ) {
+        match event {
+
+            // the day pressed, which must be the released one
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                self.state.pressed = self.date_at(layout.bounds(), cursor_position);
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                let pressed = self.state.pressed.take();
+
+                if let Some(date) = self.date_at(layout.bounds(), cursor_position) {
+                    if pressed == Some(date) {
+                        messages.push((self.on_select)(date));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a, Message, B> Into<Element<'a, Message, Renderer<B>>> for Heatmap<'a, Message>
+where
+    B: Backend,
+    Message: 'a,
+{
+    fn into(self) -> Element<'a, Message, Renderer<B>> {
+        Element::new(self)
+    }
+}
+
+/// The state of a [`Heatmap`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct State {
+
+    /// the day where the left button was pressed
+    pressed: Option<NaiveDate>,
+}
+
+impl State {
+
+    /// Creates a new [`State`], nothing pressed
+    pub fn new() -> State {
+        State::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headless::Headless;
+    use iced_native::{Cache, UserInterface};
+
+    fn center(bounds: Rectangle) -> Point {
+        Point::new(bounds.center_x(), bounds.center_y())
+    }
+
+    #[test]
+    fn color_of_empty_values() {
+        let scale = ColorScale::default();
+
+        assert_eq!(scale.color(0.0, 8.0), scale.empty);
+        assert_eq!(scale.color(-1.0, 8.0), scale.empty);
+        assert_eq!(scale.color(3.0, 0.0), scale.empty);
+        assert_eq!(ColorScale::new(Color::WHITE, Vec::new()).color(3.0, 8.0), Color::WHITE);
+    }
+
+    #[test]
+    fn color_step_boundaries() {
+        let scale = ColorScale::default();
+
+        // four steps of 2 up to 8, each including its upper bound
+        assert_eq!(scale.color(0.1, 8.0), scale.steps[0]);
+        assert_eq!(scale.color(2.0, 8.0), scale.steps[0]);
+        assert_eq!(scale.color(2.01, 8.0), scale.steps[1]);
+        assert_eq!(scale.color(4.0, 8.0), scale.steps[1]);
+        assert_eq!(scale.color(6.0, 8.0), scale.steps[2]);
+        assert_eq!(scale.color(7.9, 8.0), scale.steps[3]);
+        assert_eq!(scale.color(8.0, 8.0), scale.steps[3]);
+    }
+
+    #[test]
+    fn color_above_set_max() {
+        let scale = ColorScale::default().max(4.0);
+
+        assert_eq!(scale.color(1.0, 100.0), scale.steps[0]);
+        assert_eq!(scale.color(4.0, 100.0), scale.steps[3]);
+        assert_eq!(scale.color(50.0, 100.0), scale.steps[3]);
+    }
+
+    #[test]
+    fn date_at_year_edges() {
+        let values = HashMap::new();
+        let mut state = State::new();
+        let heatmap = Heatmap::<()>::new(2020, &mut state, &values, |_| ());
+        let bounds = Rectangle { x: 10.0, y: 20.0, width: 1000.0, height: 200.0 };
+
+        // 1 January 2020 is a Wednesday: the days before it in its week are empty
+        let first = NaiveDate::from_ymd(2020, 1, 1);
+        assert_eq!(heatmap.date_at(bounds, center(heatmap.cell_bounds(bounds, first))), Some(first));
+        assert_eq!(heatmap.date_at(bounds, center(heatmap.cell_bounds(bounds, first.pred()))), None);
+
+        // 31 December 2020 is a Thursday: so are the days after it
+        let last = NaiveDate::from_ymd(2020, 12, 31);
+        assert_eq!(heatmap.date_at(bounds, center(heatmap.cell_bounds(bounds, last))), Some(last));
+        assert_eq!(heatmap.date_at(bounds, center(heatmap.cell_bounds(bounds, last.succ()))), None);
+    }
+
+    #[test]
+    fn date_at_margins() {
+        let values = HashMap::new();
+        let mut state = State::new();
+        let heatmap = Heatmap::<()>::new(2020, &mut state, &values, |_| ());
+        let bounds = Rectangle { x: 0.0, y: 0.0, width: 1000.0, height: 200.0 };
+
+        // the weekday names, the month names, and under Sunday
+        assert_eq!(heatmap.date_at(bounds, Point::new(5.0, 30.0)), None);
+        assert_eq!(heatmap.date_at(bounds, Point::new(100.0, 5.0)), None);
+        assert_eq!(heatmap.date_at(bounds, Point::new(100.0, 18.0 + 7.0 * 12.0 + 1.0)), None);
+    }
+
+    /// the center of the cell of the date in a heatmap laid out at the origin
+    fn cell(date: NaiveDate) -> Point {
+        let values = HashMap::new();
+        let mut state = State::new();
+        let heatmap = Heatmap::<()>::new(2020, &mut state, &values, |_| ());
+
+        center(heatmap.cell_bounds(Rectangle::new(Point::ORIGIN, Size::new(1000.0, 200.0)), date))
+    }
+
+    /// feeds the event to a heatmap of 2020, with the cursor at the position
+    fn event(state: &mut State, event: mouse::Event, position: Point) -> Vec<NaiveDate> {
+        let values = HashMap::new();
+        let mut renderer = Renderer::new(Headless);
+        let heatmap = Heatmap::new(2020, state, &values, |date| date);
+        let mut user_interface = UserInterface::build(heatmap, Size::new(1000.0, 200.0), Cache::new(), &mut renderer);
+
+        user_interface.update(vec![Event::Mouse(event)], position, None, &renderer)
+    }
+
+    #[test]
+    fn click_selects_a_day() {
+        let mut state = State::new();
+        let day = NaiveDate::from_ymd(2020, 6, 5);
+
+        assert!(event(&mut state, mouse::Event::ButtonPressed(mouse::Button::Left), cell(day)).is_empty());
+        assert_eq!(event(&mut state, mouse::Event::ButtonReleased(mouse::Button::Left), cell(day)), vec![day]);
+    }
+
+    #[test]
+    fn drag_onto_a_day_selects_nothing() {
+        let mut state = State::new();
+        let (from, to) = (NaiveDate::from_ymd(2020, 6, 5), NaiveDate::from_ymd(2020, 6, 12));
+
+        event(&mut state, mouse::Event::ButtonPressed(mouse::Button::Left), cell(from));
+        assert!(event(&mut state, mouse::Event::ButtonReleased(mouse::Button::Left), cell(to)).is_empty());
+
+        // pressed out of the heatmap
+        event(&mut state, mouse::Event::ButtonPressed(mouse::Button::Left), Point::new(1.0, 1.0));
+        assert!(event(&mut state, mouse::Event::ButtonReleased(mouse::Button::Left), cell(to)).is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+///
+/// Holidays shown in the DatePicker
+///
+use chrono::prelude::*;
+use chrono::Duration;
+
+use crate::dates::nth_weekday;
+
+/// A named holiday
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+/// A source of holidays for the [`DatePicker`]
+pub trait HolidayProvider {
+
+    /// the holidays which fall in the given month
+    fn holidays(&self, year: i32, month: u32) -> Vec<Holiday>;
+}
+
+/// How the date of a holiday is found in a year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+
+    /// the same day every year, e.g. 25 December
+    Fixed { month: u32, day: u32 },
+
+    /// the nth weekday of a month, e.g. the 4th Thursday of November.
+    /// a negative n counts from the end of the month: -1 is the last one
+    NthWeekday { month: u32, weekday: Weekday, n: i32 },
+
+    /// a number of days before (negative) or after Easter Sunday
+    Easter { offset: i64 },
+}
+
+impl Rule {
+
+    /// the date of the holiday in the given year
+    pub fn date(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            Rule::Fixed { month, day } => NaiveDate::from_ymd_opt(year, month, day),
+            Rule::NthWeekday { month, weekday, n } => nth_weekday(year, month, weekday, n),
+            Rule::Easter { offset } => easter(year).map(|date| date + Duration::days(offset)),
+        }
+    }
+}
+
+/// A [`HolidayProvider`] made of named rules
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    rules: Vec<(String, Rule)>,
+}
+
+impl RuleSet {
+
+    /// create an empty [`RuleSet`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add a holiday to the [`RuleSet`]
+    pub fn rule(mut self, name: &str, rule: Rule) -> Self {
+        self.rules.push((String::from(name), rule));
+        self
+    }
+
+    /// federal holidays of the United States
+    pub fn united_states() -> Self {
+        Self::new()
+            .rule("New Year's Day", Rule::Fixed { month: 1, day: 1 })
+            .rule("Martin Luther King Jr. Day", Rule::NthWeekday { month: 1, weekday: Weekday::Mon, n: 3 })
+            .rule("Washington's Birthday", Rule::NthWeekday { month: 2, weekday: Weekday::Mon, n: 3 })
+            .rule("Memorial Day", Rule::NthWeekday { month: 5, weekday: Weekday::Mon, n: -1 })
+            .rule("Independence Day", Rule::Fixed { month: 7, day: 4 })
+            .rule("Labor Day", Rule::NthWeekday { month: 9, weekday: Weekday::Mon, n: 1 })
+            .rule("Columbus Day", Rule::NthWeekday { month: 10, weekday: Weekday::Mon, n: 2 })
+            .rule("Veterans Day", Rule::Fixed { month: 11, day: 11 })
+            .rule("Thanksgiving Day", Rule::NthWeekday { month: 11, weekday: Weekday::Thu, n: 4 })
+            .rule("Christmas Day", Rule::Fixed { month: 12, day: 25 })
+    }
+
+    /// bank holidays of England and Wales
+    pub fn united_kingdom() -> Self {
+        Self::new()
+            .rule("New Year's Day", Rule::Fixed { month: 1, day: 1 })
+            .rule("Good Friday", Rule::Easter { offset: -2 })
+            .rule("Easter Monday", Rule::Easter { offset: 1 })
+            .rule("Early May Bank Holiday", Rule::NthWeekday { month: 5, weekday: Weekday::Mon, n: 1 })
+            .rule("Spring Bank Holiday", Rule::NthWeekday { month: 5, weekday: Weekday::Mon, n: -1 })
+            .rule("Summer Bank Holiday", Rule::NthWeekday { month: 8, weekday: Weekday::Mon, n: -1 })
+            .rule("Christmas Day", Rule::Fixed { month: 12, day: 25 })
+            .rule("Boxing Day", Rule::Fixed { month: 12, day: 26 })
+    }
+
+    /// nationwide public holidays of Germany
+    pub fn germany() -> Self {
+        Self::new()
+            .rule("New Year's Day", Rule::Fixed { month: 1, day: 1 })
+            .rule("Good Friday", Rule::Easter { offset: -2 })
+            .rule("Easter Monday", Rule::Easter { offset: 1 })
+            .rule("Labour Day", Rule::Fixed { month: 5, day: 1 })
+            .rule("Ascension Day", Rule::Easter { offset: 39 })
+            .rule("Whit Monday", Rule::Easter { offset: 50 })
+            .rule("German Unity Day", Rule::Fixed { month: 10, day: 3 })
+            .rule("Christmas Day", Rule::Fixed { month: 12, day: 25 })
+            .rule("St. Stephen's Day", Rule::Fixed { month: 12, day: 26 })
+    }
+
+    /// public holidays of France
+    pub fn france() -> Self {
+        Self::new()
+            .rule("New Year's Day", Rule::Fixed { month: 1, day: 1 })
+            .rule("Easter Monday", Rule::Easter { offset: 1 })
+            .rule("Labour Day", Rule::Fixed { month: 5, day: 1 })
+            .rule("Victory in Europe Day", Rule::Fixed { month: 5, day: 8 })
+            .rule("Ascension Day", Rule::Easter { offset: 39 })
+            .rule("Whit Monday", Rule::Easter { offset: 50 })
+            .rule("Bastille Day", Rule::Fixed { month: 7, day: 14 })
+            .rule("Assumption of Mary", Rule::Fixed { month: 8, day: 15 })
+            .rule("All Saints' Day", Rule::Fixed { month: 11, day: 1 })
+            .rule("Armistice Day", Rule::Fixed { month: 11, day: 11 })
+            .rule("Christmas Day", Rule::Fixed { month: 12, day: 25 })
+    }
+}
+
+impl HolidayProvider for RuleSet {
+    fn holidays(&self, year: i32, month: u32) -> Vec<Holiday> {
+        let mut holidays: Vec<Holiday> = self.rules
+            .iter()
+            .filter_map(|(name, rule)| rule.date(year).map(|date| Holiday {
+                date,
+                name: name.clone(),
+            }))
+            .filter(|holiday| holiday.date.month() == month)
+            .collect();
+
+        holidays.sort_by_key(|holiday| holiday.date);
+        holidays
+    }
+}
+
+/// Easter Sunday of the given year (Gregorian calendar),
+/// using the anonymous Gregorian algorithm
+pub fn easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the holidays of the whole year, as (month, day, name)
+    fn year(set: &RuleSet, year: i32) -> Vec<(u32, u32, String)> {
+        (1..=12)
+            .flat_map(|month| set.holidays(year, month))
+            .map(|holiday| (holiday.date.month(), holiday.date.day(), holiday.name))
+            .collect()
+    }
+
+    fn expected(holidays: &[(u32, u32, &str)]) -> Vec<(u32, u32, String)> {
+        holidays
+            .iter()
+            .map(|(month, day, name)| (*month, *day, String::from(*name)))
+            .collect()
+    }
+
+    #[test]
+    fn easter_sundays() {
+        assert_eq!(easter(2019), Some(NaiveDate::from_ymd(2019, 4, 21)));
+        assert_eq!(easter(2020), Some(NaiveDate::from_ymd(2020, 4, 12)));
+        assert_eq!(easter(2038), Some(NaiveDate::from_ymd(2038, 4, 25)));
+
+        // the earliest and latest possible dates
+        assert_eq!(easter(2285), Some(NaiveDate::from_ymd(2285, 3, 22)));
+        assert_eq!(easter(1943), Some(NaiveDate::from_ymd(1943, 4, 25)));
+    }
+
+    #[test]
+    fn thanksgiving_is_the_fourth_thursday() {
+        let rule = Rule::NthWeekday { month: 11, weekday: Weekday::Thu, n: 4 };
+
+        assert_eq!(rule.date(2019), Some(NaiveDate::from_ymd(2019, 11, 28)));
+        assert_eq!(rule.date(2020), Some(NaiveDate::from_ymd(2020, 11, 26)));
+
+        // November 2018 starts on a Thursday
+        assert_eq!(rule.date(2018), Some(NaiveDate::from_ymd(2018, 11, 22)));
+    }
+
+    #[test]
+    fn memorial_day_is_the_last_monday() {
+        let rule = Rule::NthWeekday { month: 5, weekday: Weekday::Mon, n: -1 };
+
+        assert_eq!(rule.date(2020), Some(NaiveDate::from_ymd(2020, 5, 25)));
+
+        // May 2021 ends on a Monday
+        assert_eq!(rule.date(2021), Some(NaiveDate::from_ymd(2021, 5, 31)));
+    }
+
+    #[test]
+    fn missing_nth_weekday() {
+        // February 2021 has four Mondays only
+        assert_eq!(Rule::NthWeekday { month: 2, weekday: Weekday::Mon, n: 5 }.date(2021), None);
+        assert_eq!(Rule::NthWeekday { month: 2, weekday: Weekday::Mon, n: -5 }.date(2021), None);
+        assert_eq!(Rule::NthWeekday { month: 2, weekday: Weekday::Mon, n: 0 }.date(2021), None);
+        assert_eq!(Rule::Fixed { month: 2, day: 29 }.date(2021), None);
+    }
+
+    #[test]
+    fn united_kingdom_2019() {
+        assert_eq!(year(&RuleSet::united_kingdom(), 2019), expected(&[
+            (1, 1, "New Year's Day"),
+            (4, 19, "Good Friday"),
+            (4, 22, "Easter Monday"),
+            (5, 6, "Early May Bank Holiday"),
+            (5, 27, "Spring Bank Holiday"),
+            (8, 26, "Summer Bank Holiday"),
+            (12, 25, "Christmas Day"),
+            (12, 26, "Boxing Day"),
+        ]));
+    }
+
+    #[test]
+    fn germany_2019() {
+        assert_eq!(year(&RuleSet::germany(), 2019), expected(&[
+            (1, 1, "New Year's Day"),
+            (4, 19, "Good Friday"),
+            (4, 22, "Easter Monday"),
+            (5, 1, "Labour Day"),
+            (5, 30, "Ascension Day"),
+            (6, 10, "Whit Monday"),
+            (10, 3, "German Unity Day"),
+            (12, 25, "Christmas Day"),
+            (12, 26, "St. Stephen's Day"),
+        ]));
+    }
+
+    #[test]
+    fn france_2019() {
+        assert_eq!(year(&RuleSet::france(), 2019), expected(&[
+            (1, 1, "New Year's Day"),
+            (4, 22, "Easter Monday"),
+            (5, 1, "Labour Day"),
+            (5, 8, "Victory in Europe Day"),
+            (5, 30, "Ascension Day"),
+            (6, 10, "Whit Monday"),
+            (7, 14, "Bastille Day"),
+            (8, 15, "Assumption of Mary"),
+            (11, 1, "All Saints' Day"),
+            (11, 11, "Armistice Day"),
+            (12, 25, "Christmas Day"),
+        ]));
+    }
+}
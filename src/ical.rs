@@ -0,0 +1,602 @@
+///
+/// iCalendar (RFC 5545) support
+///
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use chrono::prelude::*;
+use chrono::Duration;
+
+use crate::date_picker::Selection;
+use crate::events::CalendarEvent;
+use crate::recurrence::Recurrence;
+
+/// the longest a content line may be, in octets, before it is folded
+const MAX_LINE_LENGTH: usize = 75;
+
+/// An error while reading an iCalendar file
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+
+    /// a VEVENT without DTSTART, at the given line
+    MissingStart(usize),
+
+    /// a date, date-time or duration that can't be read, at the given line
+    InvalidValue(usize, String),
+
+    /// a BEGIN:VEVENT without its END:VEVENT
+    Unterminated,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "cannot read the calendar: {}", error),
+            Error::MissingStart(line) => write!(f, "line {}: event without DTSTART", line),
+            Error::InvalidValue(line, value) => write!(f, "line {}: invalid value `{}`", line, value),
+            Error::Unterminated => write!(f, "event without END:VEVENT"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+/// A content line: `NAME;PARAM=VALUE:VALUE`
+struct Property {
+    line: usize,
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+
+    /// the value of a parameter
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// read the events of an iCalendar file
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Vec<CalendarEvent>, Error> {
+    parse(&fs::read_to_string(path)?)
+}
+
+/// read the events of an iCalendar document.
+/// only VEVENT components are read, everything else is skipped.
+///
+/// time zones aren't resolved: times with a TZID are read as local
+/// wall-clock times, so an event of another zone keeps its hours
+/// and may fall on another day than where the reader is
+pub fn parse(input: &str) -> Result<Vec<CalendarEvent>, Error> {
+    let mut events = Vec::new();
+    let mut event: Option<Vec<Property>> = None;
+
+    // depth of components nested in the current event, e.g. VALARM.
+    // a stray END doesn't take it below the event itself
+    let mut nested: usize = 0;
+
+    for property in unfold(input).into_iter().filter_map(parse_line) {
+        let component = property.value.trim().to_uppercase();
+
+        match (property.name.as_str(), component.as_str()) {
+            ("BEGIN", "VEVENT") if event.is_none() => event = Some(Vec::new()),
+            ("END", "VEVENT") if nested == 0 => {
+                if let Some(properties) = event.take() {
+                    events.push(to_event(&properties, property.line)?);
+                }
+            }
+            ("BEGIN", _) if event.is_some() => nested += 1,
+            ("END", _) if event.is_some() => nested = nested.saturating_sub(1),
+            _ => {
+                if let Some(properties) = event.as_mut() {
+                    if nested == 0 {
+                        properties.push(property);
+                    }
+                }
+            }
+        }
+    }
+
+    if event.is_some() {
+        return Err(Error::Unterminated)
+    }
+
+    Ok(events)
+}
+
+/// join the folded lines, keeping the number of the first line of each
+fn unfold(input: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some((_, last)) = lines.last_mut() {
+                last.push_str(&line[1..]);
+                continue
+            }
+        }
+
+        if !line.is_empty() {
+            lines.push((i + 1, String::from(line)));
+        }
+    }
+
+    lines
+}
+
+/// split a content line into its name, parameters and value
+fn parse_line((line, content): (usize, String)) -> Option<Property> {
+
+    // the value starts at the first colon outside of a quoted parameter
+    let mut quoted = false;
+    let colon = content.char_indices().find(|&(_, c)| {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        c == ':' && !quoted
+    })?.0;
+
+    let mut parts = content[..colon].split(';');
+    let name = parts.next()?.trim().to_uppercase();
+    let params = parts
+        .filter_map(|param| {
+            let mut pair = param.splitn(2, '=');
+            let key = pair.next()?.trim().to_uppercase();
+            let value = pair.next()?.trim_matches('"').to_string();
+            Some((key, value))
+        })
+        .collect();
+
+    Some(Property {
+        line,
+        name,
+        params,
+        value: content[colon + 1..].to_string(),
+    })
+}
+
+/// build a [`CalendarEvent`] from the properties of a VEVENT
+fn to_event(properties: &[Property], end_line: usize) -> Result<CalendarEvent, Error> {
+    let find = |name: &str| properties.iter().find(|p| p.name == name);
+
+    let start = find("DTSTART").ok_or(Error::MissingStart(end_line))?;
+    let (start, all_day) = parse_date_time(start)?;
+
+    let end = if let Some(end) = find("DTEND") {
+        parse_date_time(end)?.0
+    } else if let Some(duration) = find("DURATION") {
+        start + parse_duration(duration)?
+    } else if all_day {
+        // an all-day event without an end lasts one day
+        start + Duration::days(1)
+    } else {
+        start
+    };
+
+    // rules which can't be evaluated leave the event as a single one
+    let recurrence = find("RRULE")
+        .and_then(|rule| rule.value.parse().ok())
+        .map(|rule| {
+            properties
+                .iter()
+                .filter(|p| p.name == "EXDATE")
+                .flat_map(|p| p.value.split(','))
+                .filter_map(|value| value.trim().get(..8))
+                .filter_map(|value| NaiveDate::parse_from_str(value, "%Y%m%d").ok())
+                .fold(Recurrence::new(start.date(), rule), Recurrence::except)
+        });
+
+    Ok(CalendarEvent {
+        summary: find("SUMMARY").map(|p| unescape(&p.value)).unwrap_or_default(),
+        description: find("DESCRIPTION").map(|p| unescape(&p.value)),
+        start,
+        end: end.max(start),
+        all_day,
+        recurrence,
+    })
+}
+
+/// read a DATE or DATE-TIME value. the flag is true for a DATE.
+/// UTC times are converted to local time, other times are taken as local
+/// wall-clock times, whatever their TZID
+fn parse_date_time(property: &Property) -> Result<(NaiveDateTime, bool), Error> {
+    let value = property.value.trim();
+    let invalid = || Error::InvalidValue(property.line, value.to_string());
+
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| invalid())?;
+        return Ok((date.and_hms(0, 0, 0), true))
+    }
+
+    if let Some(value) = value.strip_suffix('Z') {
+        let utc = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+        let local = Utc.from_utc_datetime(&utc).with_timezone(&Local);
+        return Ok((local.naive_local(), false))
+    }
+
+    let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+    Ok((local, false))
+}
+
+/// read a DURATION value, e.g. `P1W`, `P2D`, `PT1H30M`
+fn parse_duration(property: &Property) -> Result<Duration, Error> {
+    let value = property.value.trim();
+    let invalid = || Error::InvalidValue(property.line, value.to_string());
+
+    let (negative, rest) = match value.chars().next() {
+        Some('-') => (true, &value[1..]),
+        Some('+') => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+
+    let mut duration = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => in_time = true,
+            _ => {
+                let n: i64 = number.parse().map_err(|_| invalid())?;
+                number.clear();
+
+                duration = duration + match (c, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return Err(invalid()),
+                };
+            }
+        }
+    }
+
+    if !number.is_empty() {
+        return Err(invalid())
+    }
+
+    Ok(if negative { -duration } else { duration })
+}
+
+/// undo the escaping of a TEXT value
+fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => text.push('\n'),
+                Some(other) => text.push(other),
+                None => text.push('\\'),
+            }
+        } else {
+            text.push(c);
+        }
+    }
+
+    text
+}
+
+/// write a selection of the [`DatePicker`] as an iCalendar document.
+/// a single date or a range becomes one all-day event,
+/// multiple dates become one all-day event each.
+/// None without dates, a calendar needing at least one event
+pub fn export(selection: &Selection, title: Option<&str>, description: Option<&str>) -> Option<String> {
+    let ranges: Vec<(NaiveDate, NaiveDate)> = match selection {
+        Selection::Single(date) => vec![(*date, *date)],
+        Selection::Range(start, end) => vec![(*start.min(end), *start.max(end))],
+        Selection::Multiple(dates) => dates.iter().map(|date| (*date, *date)).collect(),
+    };
+    if ranges.is_empty() {
+        return None
+    }
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//date_picker//DatePicker//EN"),
+        String::from("CALSCALE:GREGORIAN"),
+    ];
+
+    for (i, (start, end)) in ranges.iter().enumerate() {
+        lines.push(String::from("BEGIN:VEVENT"));
+        lines.push(format!("UID:{}-{}-{}@date_picker", stamp, i, start.format("%Y%m%d")));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")));
+
+        // the end of an all-day event is exclusive
+        lines.push(format!("DTEND;VALUE=DATE:{}", end.succ().format("%Y%m%d")));
+
+        if let Some(title) = title {
+            lines.push(format!("SUMMARY:{}", escape(title)));
+        }
+        if let Some(description) = description {
+            lines.push(format!("DESCRIPTION:{}", escape(description)));
+        }
+        lines.push(String::from("END:VEVENT"));
+    }
+
+    lines.push(String::from("END:VCALENDAR"));
+
+    Some(lines.iter().map(|line| fold(line)).collect())
+}
+
+/// escape a TEXT value
+fn escape(text: &str) -> String {
+    let mut value = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                value.push('\\');
+                value.push(c);
+            }
+            '\n' => value.push_str("\\n"),
+            '\r' => {}
+            _ => value.push(c),
+        }
+    }
+
+    value
+}
+
+/// split a content line into lines of at most 75 octets,
+/// without breaking a character, each ended by CRLF
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a calendar of the given content lines, ended by CRLF
+    fn calendar(lines: &[&str]) -> String {
+        let mut document = String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n");
+        for line in lines {
+            document.push_str(line);
+            document.push_str("\r\n");
+        }
+        document.push_str("END:VCALENDAR\r\n");
+        document
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd(year, month, day)
+    }
+
+    #[test]
+    fn all_day_event() {
+        let events = parse(&calendar(&[
+            "BEGIN:VEVENT",
+            "SUMMARY:Release",
+            "DTSTART;VALUE=DATE:20200605",
+            "END:VEVENT",
+        ])).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "Release");
+        assert!(events[0].all_day);
+        assert_eq!(events[0].first_day(), date(2020, 6, 5));
+        assert_eq!(events[0].last_day(), date(2020, 6, 5));
+    }
+
+    #[test]
+    fn multi_day_event_ends_before_dtend() {
+        let events = parse(&calendar(&[
+            "BEGIN:VEVENT",
+            "DTSTART;VALUE=DATE:20200605",
+            "DTEND;VALUE=DATE:20200608",
+            "END:VEVENT",
+        ])).unwrap();
+
+        assert_eq!(events[0].first_day(), date(2020, 6, 5));
+        assert_eq!(events[0].last_day(), date(2020, 6, 7));
+        assert!(events[0].occurs_on(date(2020, 6, 7)));
+        assert!(!events[0].occurs_on(date(2020, 6, 8)));
+    }
+
+    #[test]
+    fn timed_event_with_duration() {
+        let events = parse(&calendar(&[
+            "BEGIN:VEVENT",
+            "DTSTART:20200605T093000",
+            "DURATION:PT1H30M",
+            "END:VEVENT",
+        ])).unwrap();
+
+        assert!(!events[0].all_day);
+        assert_eq!(events[0].start, date(2020, 6, 5).and_hms(9, 30, 0));
+        assert_eq!(events[0].end, date(2020, 6, 5).and_hms(11, 0, 0));
+    }
+
+    #[test]
+    fn zoned_time_is_wall_clock() {
+        let events = parse(&calendar(&[
+            "BEGIN:VEVENT",
+            "DTSTART;TZID=\"Europe/Paris\":20200605T233000",
+            "END:VEVENT",
+        ])).unwrap();
+
+        assert_eq!(events[0].start, date(2020, 6, 5).and_hms(23, 30, 0));
+    }
+
+    #[test]
+    fn folded_lines() {
+        let events = parse(&calendar(&[
+            "BEGIN:VEVENT",
+            "SUMMARY:Quarter",
+            " ly plan",
+            "\tning",
+            "DTSTART;VALUE=DATE:2020",
+            " 0605",
+            "END:VEVENT",
+        ])).unwrap();
+
+        assert_eq!(events[0].summary, "Quarterly planning");
+        assert_eq!(events[0].first_day(), date(2020, 6, 5));
+    }
+
+    #[test]
+    fn escaped_text() {
+        let events = parse(&calendar(&[
+            "BEGIN:VEVENT",
+            "SUMMARY:Lunch\\, then a walk\\; maybe",
+            "DESCRIPTION:C:\\\\Calendar\\nsecond line\\Nthird",
+            "DTSTART;VALUE=DATE:20200605",
+            "END:VEVENT",
+        ])).unwrap();
+
+        assert_eq!(events[0].summary, "Lunch, then a walk; maybe");
+        assert_eq!(events[0].description.as_deref(), Some("C:\\Calendar\nsecond line\nthird"));
+    }
+
+    #[test]
+    fn nested_components_are_skipped() {
+        let events = parse(&calendar(&[
+            "BEGIN:VEVENT",
+            "SUMMARY:Dentist",
+            "DTSTART;VALUE=DATE:20200605",
+            "BEGIN:VALARM",
+            "SUMMARY:Reminder",
+            "END:VALARM",
+            "END:VEVENT",
+        ])).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "Dentist");
+    }
+
+    #[test]
+    fn stray_end_keeps_later_events() {
+        let events = parse(&calendar(&[
+            "BEGIN:VEVENT",
+            "SUMMARY:First",
+            "DTSTART;VALUE=DATE:20200605",
+            "END:VALARM",
+            "END:VEVENT",
+            "BEGIN:VEVENT",
+            "SUMMARY:Second",
+            "DTSTART;VALUE=DATE:20200606",
+            "END:VEVENT",
+        ])).unwrap();
+
+        let summaries: Vec<&str> = events.iter().map(|event| event.summary.as_str()).collect();
+        assert_eq!(summaries, ["First", "Second"]);
+    }
+
+    #[test]
+    fn invalid_events() {
+        let missing_start = parse(&calendar(&["BEGIN:VEVENT", "SUMMARY:Nothing", "END:VEVENT"]));
+        assert!(matches!(missing_start, Err(Error::MissingStart(5))));
+
+        let invalid = parse(&calendar(&["BEGIN:VEVENT", "DTSTART:2020-06-05", "END:VEVENT"]));
+        assert!(matches!(invalid, Err(Error::InvalidValue(4, _))));
+
+        let unterminated = parse("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nDTSTART:20200605T093000\r\n");
+        assert!(matches!(unterminated, Err(Error::Unterminated)));
+    }
+
+    /// the first and last days of the events of the document
+    fn round_trip(selection: &Selection) -> Vec<(NaiveDate, NaiveDate)> {
+        parse(&export(selection, Some("Trip"), None).unwrap())
+            .unwrap()
+            .iter()
+            .map(|event| (event.first_day(), event.last_day()))
+            .collect()
+    }
+
+    #[test]
+    fn export_single() {
+        assert_eq!(round_trip(&Selection::Single(date(2020, 6, 5))), [(date(2020, 6, 5), date(2020, 6, 5))]);
+    }
+
+    #[test]
+    fn export_range() {
+        assert_eq!(
+            round_trip(&Selection::Range(date(2020, 12, 30), date(2021, 1, 2))),
+            [(date(2020, 12, 30), date(2021, 1, 2))],
+        );
+
+        // a range picked backwards
+        assert_eq!(
+            round_trip(&Selection::Range(date(2021, 1, 2), date(2020, 12, 30))),
+            [(date(2020, 12, 30), date(2021, 1, 2))],
+        );
+    }
+
+    #[test]
+    fn export_multiple() {
+        let dates = vec![date(2020, 2, 28), date(2020, 2, 29), date(2020, 3, 10)];
+
+        assert_eq!(
+            round_trip(&Selection::Multiple(dates.clone())),
+            dates.iter().map(|date| (*date, *date)).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn export_nothing() {
+        assert_eq!(export(&Selection::Multiple(Vec::new()), Some("Trip"), None), None);
+    }
+
+    #[test]
+    fn export_escapes_text() {
+        let title = "Lunch, then a walk; C:\\Calendar";
+        let description = "first line\nsecond line, \\n not a newline";
+        let document = export(&Selection::Single(date(2020, 6, 5)), Some(title), Some(description)).unwrap();
+
+        assert!(document.contains("SUMMARY:Lunch\\, then a walk\\; C:\\\\Calendar\r\n"));
+
+        let events = parse(&document).unwrap();
+        assert_eq!(events[0].summary, title);
+        assert_eq!(events[0].description.as_deref(), Some(description));
+    }
+
+    #[test]
+    fn export_folds_long_lines() {
+        // two-octet and three-octet characters cross the 75th octet
+        let title = "Réunion trimestrielle — équipe données, planification annuelle, café offert";
+        let document = export(&Selection::Single(date(2020, 6, 5)), Some(title), None).unwrap();
+
+        assert!(document.ends_with("END:VCALENDAR\r\n"));
+        let lines: Vec<&str> = document.trim_end_matches("\r\n").split("\r\n").collect();
+        assert!(lines.iter().all(|line| line.len() <= MAX_LINE_LENGTH));
+        assert!(lines.iter().any(|line| line.starts_with(' ')));
+
+        assert_eq!(parse(&document).unwrap()[0].summary, title);
+    }
+}
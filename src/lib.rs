@@ -0,0 +1,21 @@
+///
+/// The DatePicker widget, and the views and helpers built around it
+///
+pub mod accessibility;
+pub mod agenda;
+pub mod date_picker;
+pub mod dates;
+pub mod events;
+pub mod headless;
+pub mod heatmap;
+pub mod holiday;
+#[cfg(feature = "ical")]
+pub mod ical;
+pub mod parsing;
+pub mod presets;
+pub mod recurrence;
+pub mod simulation;
+pub mod snapshot;
+mod style;
+pub mod svg;
+pub mod week_view;
@@ -0,0 +1,347 @@
+///
+/// Parsing of the dates typed in the DatePicker's input:
+/// strict formats, and relative expressions such as "next friday"
+///
+use chrono::prelude::*;
+use chrono::Duration;
+
+use crate::dates::{add_months, last_day_of_month, week_start};
+
+/// Turns the text typed in the input of a [`DatePicker`] into a date.
+/// Closures taking the text and today are parsers too,
+/// and a pair of parsers tries the second one when the first fails
+pub trait DateParser {
+
+    /// the date the text stands for, given today;
+    /// none when the text isn't understood
+    fn parse(&self, text: &str, today: NaiveDate) -> Option<NaiveDate>;
+}
+
+impl<F> DateParser for F
+where
+    F: Fn(&str, NaiveDate) -> Option<NaiveDate>,
+{
+    fn parse(&self, text: &str, today: NaiveDate) -> Option<NaiveDate> {
+        self(text, today)
+    }
+}
+
+impl<A, B> DateParser for (A, B)
+where
+    A: DateParser,
+    B: DateParser,
+{
+    fn parse(&self, text: &str, today: NaiveDate) -> Option<NaiveDate> {
+        self.0.parse(text, today).or_else(|| self.1.parse(text, today))
+    }
+}
+
+/// The parser of a [`DatePicker`] unless it is set:
+/// the format of the picker, then relative expressions
+pub fn standard() -> (Formats, Relative) {
+    (Formats::iso(), Relative)
+}
+
+/// Dates written in exact `chrono` formats, e.g. "%d/%m/%Y",
+/// tried in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Formats(pub Vec<String>);
+
+impl Formats {
+
+    /// the given formats
+    pub fn new(formats: &[&str]) -> Self {
+        Formats(formats.iter().map(|format| String::from(*format)).collect())
+    }
+
+    /// the format of the picker, e.g. 2020-06-05
+    pub fn iso() -> Self {
+        Formats::new(&["%Y-%m-%d"])
+    }
+}
+
+impl DateParser for Formats {
+    fn parse(&self, text: &str, _today: NaiveDate) -> Option<NaiveDate> {
+        self.0
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(text.trim(), format).ok())
+    }
+}
+
+/// Relative expressions in English, whatever their case,
+/// "the" being ignored:
+///
+/// - "today", "now", "tomorrow", "yesterday"
+/// - a weekday, "fri" or "friday": the coming one, today included
+/// - "next friday", "last friday": the one after or before today
+/// - "this friday": the one of the current week, from Monday
+/// - "+3d", "-2w", "+1m", "+1y", "+3": days, weeks, months or years
+///   from today, days by default; "+3 days" too
+/// - "in 3 days", "in a week", "2 weeks ago", "a month ago"
+/// - "next week", "last month", "next year": the same day then
+/// - "start of month", "beginning of the week", "end of month",
+///   "end of next month", "start of last year"
+///
+/// Months and years keep the day, or the last one of shorter months:
+/// one month after 31 January is the 29th of February in 2020
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Relative;
+
+/// A unit of time in relative expressions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl DateParser for Relative {
+    fn parse(&self, text: &str, today: NaiveDate) -> Option<NaiveDate> {
+        let text = text.to_lowercase();
+        let words: Vec<&str> = text.split_whitespace().filter(|word| *word != "the").collect();
+
+        match words.as_slice() {
+            ["today"] | ["now"] => Some(today),
+            ["tomorrow"] => today.succ_opt(),
+            ["yesterday"] => today.pred_opt(),
+
+            [offset] if is_signed(offset) => {
+                let digits = offset[1..].trim_end_matches(char::is_alphabetic);
+                let unit = match &offset[1 + digits.len()..] {
+                    "" => Unit::Day,
+                    suffix => unit(suffix)?,
+                };
+                shift(today, signed(&offset[..1 + digits.len()])?, unit)
+            }
+            [offset, word] if is_signed(offset) => shift(today, signed(offset)?, unit(word)?),
+
+            ["in", n, word] => shift(today, count(n)?, unit(word)?),
+            [n, word, "ago"] => shift(today, -count(n)?, unit(word)?),
+
+            ["next", word] => match unit(word) {
+                Some(unit) => shift(today, 1, unit),
+                None => weekday_from(today.succ_opt()?, word.parse().ok()?, 1),
+            },
+            ["last", word] => match unit(word) {
+                Some(unit) => shift(today, -1, unit),
+                None => weekday_from(today.pred_opt()?, word.parse().ok()?, -1),
+            },
+            ["this", word] => {
+                let weekday: Weekday = word.parse().ok()?;
+                week_start(today).checked_add_signed(Duration::days(weekday.num_days_from_monday() as i64))
+            }
+
+            [edge, "of", rest @ ..] => {
+                let (date, word) = match rest {
+                    [word] | ["this", word] => (Some(today), word),
+                    ["next", word] => (shift(today, 1, unit(word)?), word),
+                    ["last", word] => (shift(today, -1, unit(word)?), word),
+                    _ => return None,
+                };
+                let is_end = match *edge {
+                    "start" | "beginning" => false,
+                    "end" => true,
+                    _ => return None,
+                };
+                period_edge(date?, unit(word)?, is_end)
+            }
+
+            [word] => weekday_from(today, word.parse().ok()?, 1),
+            _ => None,
+        }
+    }
+}
+
+/// check if the word starts with a sign, e.g. "+3d"
+fn is_signed(word: &str) -> bool {
+    word.starts_with('+') || word.starts_with('-')
+}
+
+/// the signed number, e.g. "-2"
+fn signed(word: &str) -> Option<i32> {
+    let n = count(&word[1..])?;
+    Some(if word.starts_with('-') { -n } else { n })
+}
+
+/// the number of units, in digits or "a", "an", "one"
+fn count(word: &str) -> Option<i32> {
+    match word {
+        "a" | "an" | "one" => Some(1),
+
+        // small enough that no date arithmetic overflows
+        _ => word.parse::<u16>().ok().map(i32::from),
+    }
+}
+
+/// the unit, short or long, e.g. "d", "day" or "days"
+fn unit(word: &str) -> Option<Unit> {
+    match word {
+        "d" | "day" | "days" => Some(Unit::Day),
+        "w" | "week" | "weeks" => Some(Unit::Week),
+        "m" | "month" | "months" => Some(Unit::Month),
+        "y" | "year" | "years" => Some(Unit::Year),
+        _ => None,
+    }
+}
+
+/// the date n units later, before when n is negative,
+/// the day kept within shorter months
+fn shift(date: NaiveDate, n: i32, unit: Unit) -> Option<NaiveDate> {
+    let months = match unit {
+        Unit::Day => return date.checked_add_signed(Duration::days(n as i64)),
+        Unit::Week => return date.checked_add_signed(Duration::weeks(n as i64)),
+        Unit::Month => n,
+        Unit::Year => n * 12,
+    };
+
+    let (year, month) = add_months(date.year(), date.month(), months);
+    let last = last_day_of_month(year, month)?;
+    NaiveDate::from_ymd_opt(year, month, date.day().min(last.day()))
+}
+
+/// the first weekday from the date, included, going forward when
+/// step is 1 and backward when it is -1
+fn weekday_from(date: NaiveDate, weekday: Weekday, step: i64) -> Option<NaiveDate> {
+    (0..7)
+        .filter_map(|n| date.checked_add_signed(Duration::days(n * step)))
+        .find(|date| date.weekday() == weekday)
+}
+
+/// the first or last day of the week, month or year of the date
+fn period_edge(date: NaiveDate, unit: Unit, is_end: bool) -> Option<NaiveDate> {
+    match (unit, is_end) {
+        (Unit::Day, _) => Some(date),
+        (Unit::Week, false) => Some(week_start(date)),
+        (Unit::Week, true) => week_start(date).checked_add_signed(Duration::days(6)),
+        (Unit::Month, false) => date.with_day(1),
+        (Unit::Month, true) => last_day_of_month(date.year(), date.month()),
+        (Unit::Year, false) => NaiveDate::from_ymd_opt(date.year(), 1, 1),
+        (Unit::Year, true) => NaiveDate::from_ymd_opt(date.year(), 12, 31),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the date the standard parser gives on Friday 2020-06-05
+    fn parse(text: &str) -> Option<NaiveDate> {
+        standard().parse(text, NaiveDate::from_ymd(2020, 6, 5))
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        Some(NaiveDate::from_ymd(year, month, day))
+    }
+
+    #[test]
+    fn strict_formats() {
+        assert_eq!(parse("2021-02-14"), date(2021, 2, 14));
+        assert_eq!(parse(" 2021-02-14 "), date(2021, 2, 14));
+        assert_eq!(parse("2021-02-30"), None);
+
+        let formats = Formats::new(&["%d/%m/%Y", "%Y%m%d"]);
+        let today = NaiveDate::from_ymd(2020, 6, 5);
+        assert_eq!(formats.parse("14/02/2021", today), date(2021, 2, 14));
+        assert_eq!(formats.parse("20210214", today), date(2021, 2, 14));
+        assert_eq!(formats.parse("2021-02-14", today), None);
+    }
+
+    #[test]
+    fn words() {
+        assert_eq!(parse("today"), date(2020, 6, 5));
+        assert_eq!(parse("Now"), date(2020, 6, 5));
+        assert_eq!(parse("tomorrow"), date(2020, 6, 6));
+        assert_eq!(parse("yesterday"), date(2020, 6, 4));
+    }
+
+    #[test]
+    fn weekdays() {
+        assert_eq!(parse("friday"), date(2020, 6, 5));
+        assert_eq!(parse("mon"), date(2020, 6, 8));
+    }
+
+    #[test]
+    fn next_and_last_weekdays() {
+        assert_eq!(parse("next friday"), date(2020, 6, 12));
+        assert_eq!(parse("next Wednesday"), date(2020, 6, 10));
+        assert_eq!(parse("last friday"), date(2020, 5, 29));
+        assert_eq!(parse("last sat"), date(2020, 5, 30));
+    }
+
+    #[test]
+    fn weekdays_of_this_week() {
+        assert_eq!(parse("this monday"), date(2020, 6, 1));
+        assert_eq!(parse("this sunday"), date(2020, 6, 7));
+    }
+
+    #[test]
+    fn signed_offsets() {
+        assert_eq!(parse("+3d"), date(2020, 6, 8));
+        assert_eq!(parse("-2w"), date(2020, 5, 22));
+        assert_eq!(parse("+1m"), date(2020, 7, 5));
+        assert_eq!(parse("+1y"), date(2021, 6, 5));
+        assert_eq!(parse("+3"), date(2020, 6, 8));
+        assert_eq!(parse("+3 days"), date(2020, 6, 8));
+        assert_eq!(parse("-1 month"), date(2020, 5, 5));
+        assert_eq!(parse("+3x"), None);
+        assert_eq!(parse("+"), None);
+    }
+
+    #[test]
+    fn in_and_ago() {
+        assert_eq!(parse("in 3 days"), date(2020, 6, 8));
+        assert_eq!(parse("in a week"), date(2020, 6, 12));
+        assert_eq!(parse("2 weeks ago"), date(2020, 5, 22));
+        assert_eq!(parse("a month ago"), date(2020, 5, 5));
+        assert_eq!(parse("30 years ago"), date(1990, 6, 5));
+        assert_eq!(parse("3 parsecs ago"), None);
+    }
+
+    #[test]
+    fn next_and_last_units() {
+        assert_eq!(parse("next week"), date(2020, 6, 12));
+        assert_eq!(parse("last month"), date(2020, 5, 5));
+        assert_eq!(parse("next year"), date(2021, 6, 5));
+    }
+
+    #[test]
+    fn months_keep_the_day_within_shorter_months() {
+        let today = NaiveDate::from_ymd(2020, 1, 31);
+
+        assert_eq!(Relative.parse("+1m", today), date(2020, 2, 29));
+        assert_eq!(Relative.parse("next year", NaiveDate::from_ymd(2020, 2, 29)), date(2021, 2, 28));
+    }
+
+    #[test]
+    fn edges_of_periods() {
+        assert_eq!(parse("end of month"), date(2020, 6, 30));
+        assert_eq!(parse("end of the month"), date(2020, 6, 30));
+        assert_eq!(parse("start of week"), date(2020, 6, 1));
+        assert_eq!(parse("beginning of the year"), date(2020, 1, 1));
+        assert_eq!(parse("end of next month"), date(2020, 7, 31));
+        assert_eq!(parse("start of last year"), date(2019, 1, 1));
+        assert_eq!(parse("end of this week"), date(2020, 6, 7));
+        assert_eq!(parse("middle of month"), None);
+    }
+
+    #[test]
+    fn rejected_input() {
+        assert_eq!(parse("someday"), None);
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("in 99999 days"), None);
+        assert_eq!(parse("next fortnight"), None);
+    }
+
+    #[test]
+    fn pairs_try_the_second_parser() {
+        let today = NaiveDate::from_ymd(2020, 6, 5);
+        let christmas = |text: &str, today: NaiveDate| match text {
+            "christmas" => NaiveDate::from_ymd_opt(today.year(), 12, 25),
+            _ => None,
+        };
+
+        assert_eq!((Formats::iso(), christmas).parse("christmas", today), date(2020, 12, 25));
+        assert_eq!((Formats::iso(), christmas).parse("2020-06-06", today), date(2020, 6, 6));
+    }
+}
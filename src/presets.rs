@@ -0,0 +1,120 @@
+///
+/// Preset selections of the DatePicker's side panel,
+/// relative to the date taken as today
+///
+use chrono::prelude::*;
+use chrono::Duration;
+
+use crate::date_picker::Selection;
+use crate::dates::{add_months, last_day_of_month};
+
+/// A one-click selection, a date or a range ending around today
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Preset {
+    Today,
+    Yesterday,
+
+    /// the n days up to today, both included
+    LastDays(u32),
+
+    /// every day of the month of today
+    ThisMonth,
+
+    /// the calendar quarter before the one of today
+    LastQuarter,
+
+    /// from 1 January to today
+    YearToDate,
+}
+
+impl Preset {
+
+    /// the presets of reports: today, yesterday, the last 7 days,
+    /// this month, the last quarter and the year to date
+    pub fn defaults() -> Vec<Preset> {
+        vec![
+            Preset::Today,
+            Preset::Yesterday,
+            Preset::LastDays(7),
+            Preset::ThisMonth,
+            Preset::LastQuarter,
+            Preset::YearToDate,
+        ]
+    }
+
+    /// the label of the preset in the panel, e.g. "Last 7 days"
+    pub fn label(&self) -> String {
+        match self {
+            Preset::Today => String::from("Today"),
+            Preset::Yesterday => String::from("Yesterday"),
+            Preset::LastDays(1) => String::from("Last day"),
+            Preset::LastDays(n) => format!("Last {} days", n),
+            Preset::ThisMonth => String::from("This month"),
+            Preset::LastQuarter => String::from("Last quarter"),
+            Preset::YearToDate => String::from("Year to date"),
+        }
+    }
+
+    /// the first and last days the preset selects, given today,
+    /// the same day when it selects a single date
+    pub fn range(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let month_range = |(year, month): (i32, u32), months: i32| {
+            let (last_year, last_month) = add_months(year, month, months - 1);
+            (
+                NaiveDate::from_ymd(year, month, 1),
+                last_day_of_month(last_year, last_month).unwrap_or(today),
+            )
+        };
+
+        match self {
+            Preset::Today => (today, today),
+            Preset::Yesterday => (today.pred(), today.pred()),
+            Preset::LastDays(n) => (today - Duration::days(*n.max(&1) as i64 - 1), today),
+            Preset::ThisMonth => month_range((today.year(), today.month()), 1),
+            Preset::LastQuarter => {
+                let quarter = (today.month() - 1) / 3 * 3 + 1;
+                month_range(add_months(today.year(), quarter, -3), 3)
+            }
+            Preset::YearToDate => (NaiveDate::from_ymd(today.year(), 1, 1), today),
+        }
+    }
+
+    /// the date or range the preset selects, given today
+    pub fn selection(&self, today: NaiveDate) -> Selection {
+        match self.range(today) {
+            (first, last) if first == last => Selection::Single(first),
+            (first, last) => Selection::Range(first, last),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd(year, month, day)
+    }
+
+    #[test]
+    fn ranges() {
+        let today = date(2020, 2, 12);
+
+        assert_eq!(Preset::Today.range(today), (today, today));
+        assert_eq!(Preset::Yesterday.range(today), (date(2020, 2, 11), date(2020, 2, 11)));
+        assert_eq!(Preset::LastDays(7).range(today), (date(2020, 2, 6), today));
+        assert_eq!(Preset::LastDays(0).range(today), (today, today));
+        assert_eq!(Preset::ThisMonth.range(today), (date(2020, 2, 1), date(2020, 2, 29)));
+        assert_eq!(Preset::LastQuarter.range(today), (date(2019, 10, 1), date(2019, 12, 31)));
+        assert_eq!(Preset::YearToDate.range(today), (date(2020, 1, 1), today));
+    }
+
+    #[test]
+    fn single_days_are_single_selections() {
+        let today = date(2020, 2, 12);
+
+        assert_eq!(Preset::Yesterday.selection(today), Selection::Single(date(2020, 2, 11)));
+        assert_eq!(Preset::LastDays(1).selection(today), Selection::Single(today));
+        assert_eq!(Preset::ThisMonth.selection(today), Selection::Range(date(2020, 2, 1), date(2020, 2, 29)));
+    }
+}
@@ -0,0 +1,671 @@
+///
+/// Recurrence rules (RFC 5545 RRULE)
+///
+use std::cell::RefCell;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::prelude::*;
+use chrono::Duration;
+
+use crate::dates::{add_months, last_day_of_month, nth_weekday, nth_weekday_of_year, week_start, WEEKDAYS};
+
+const WEEKDAY_CODES: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+
+/// How often a rule repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Default for Frequency {
+    fn default() -> Self {
+        Frequency::Weekly
+    }
+}
+
+/// A recurrence rule, e.g. `FREQ=MONTHLY;BYDAY=2TU;COUNT=10`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+
+    /// weekdays, with an optional position in the month for monthly rules
+    /// and yearly rules with months, in the year for other yearly rules:
+    /// 2TU is the 2nd Tuesday, -1FR the last Friday
+    pub by_day: Vec<(Option<i32>, Weekday)>,
+
+    /// days of the month, negative ones count from the end of the month
+    pub by_month_day: Vec<i32>,
+
+    /// the months, from 1 to 12, the rule takes place in
+    pub by_month: Vec<u32>,
+    pub count: Option<u32>,
+
+    /// the last possible date, inclusive
+    pub until: Option<NaiveDate>,
+}
+
+impl RecurrenceRule {
+
+    /// create a rule repeating every period forever
+    pub fn new(frequency: Frequency) -> Self {
+        Self {
+            frequency,
+            interval: 1,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            count: None,
+            until: None,
+        }
+    }
+}
+
+/// An error while reading a recurrence rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingFrequency,
+
+    /// a part with a value that can't be read
+    InvalidPart(String),
+
+    /// a part this implementation doesn't evaluate, e.g. BYSETPOS
+    Unsupported(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingFrequency => write!(f, "the rule has no FREQ"),
+            ParseError::InvalidPart(part) => write!(f, "invalid rule part `{}`", part),
+            ParseError::Unsupported(part) => write!(f, "unsupported rule part `{}`", part),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl FromStr for RecurrenceRule {
+    type Err = ParseError;
+
+    /// read a rule such as `FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH`,
+    /// with or without the `RRULE:` prefix
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix("RRULE:").unwrap_or(s);
+
+        let mut frequency = None;
+        let mut rule = RecurrenceRule::new(Frequency::default());
+
+        for part in s.split(';').filter(|part| !part.is_empty()) {
+            let invalid = || ParseError::InvalidPart(part.to_string());
+
+            let mut pair = part.splitn(2, '=');
+            let name = pair.next().unwrap_or_default().to_uppercase();
+            let value = pair.next().ok_or_else(invalid)?;
+
+            match name.as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(ParseError::Unsupported(part.to_string())),
+                    });
+                }
+                "INTERVAL" => {
+                    rule.interval = value.parse().map_err(|_| invalid())?;
+                    if rule.interval == 0 {
+                        return Err(invalid())
+                    }
+                }
+                "COUNT" => rule.count = Some(value.parse().map_err(|_| invalid())?),
+                "UNTIL" => {
+                    let date = value.get(..8).ok_or_else(invalid)?;
+                    rule.until = Some(NaiveDate::parse_from_str(date, "%Y%m%d")
+                        .map_err(|_| invalid())?);
+                }
+                "BYDAY" => {
+                    rule.by_day = value
+                        .split(',')
+                        .map(|day| parse_weekday(day).ok_or_else(invalid))
+                        .collect::<Result<_, _>>()?;
+                }
+                "BYMONTHDAY" => {
+                    rule.by_month_day = value
+                        .split(',')
+                        .map(|day| match day.parse::<i32>() {
+                            Ok(day) if day != 0 && day.abs() <= 31 => Ok(day),
+                            _ => Err(invalid()),
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+                "BYMONTH" => {
+                    rule.by_month = value
+                        .split(',')
+                        .map(|month| match month.parse::<u32>() {
+                            Ok(month) if (1..=12).contains(&month) => Ok(month),
+                            _ => Err(invalid()),
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+
+                // weeks always start on Monday here
+                "WKST" => {}
+                _ => return Err(ParseError::Unsupported(part.to_string())),
+            }
+        }
+
+        rule.frequency = frequency.ok_or(ParseError::MissingFrequency)?;
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+
+    /// write the rule in the RRULE format, without the `RRULE:` prefix
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={}", frequency)?;
+
+        if self.interval > 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self.by_day
+                .iter()
+                .map(|(n, weekday)| format!(
+                    "{}{}",
+                    n.map(|n| n.to_string()).unwrap_or_default(),
+                    WEEKDAY_CODES[weekday.num_days_from_monday() as usize]
+                ))
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<String> = self.by_month_day.iter().map(|day| day.to_string()).collect();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        if !self.by_month.is_empty() {
+            let months: Vec<String> = self.by_month.iter().map(|month| month.to_string()).collect();
+            write!(f, ";BYMONTH={}", months.join(","))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// read a BYDAY value such as `TU`, `2TU` or `-1FR`
+fn parse_weekday(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let value = value.trim().to_uppercase();
+    if value.len() < 2 || !value.is_char_boundary(value.len() - 2) {
+        return None
+    }
+
+    let (n, code) = value.split_at(value.len() - 2);
+    let weekday = WEEKDAYS[WEEKDAY_CODES.iter().position(|c| *c == code)?];
+
+    let n = if n.is_empty() {
+        None
+    } else {
+        match n.parse::<i32>() {
+            Ok(n) if n != 0 && n.abs() <= 53 => Some(n),
+            _ => return None,
+        }
+    };
+
+    Some((n, weekday))
+}
+
+/// A rule starting on a date, with the dates it skips (EXDATE)
+#[derive(Debug, Clone)]
+pub struct Recurrence {
+    pub start: NaiveDate,
+    pub rule: RecurrenceRule,
+    pub exceptions: Vec<NaiveDate>,
+
+    /// the dates counted by a COUNT rule so far
+    expansion: RefCell<Expansion>,
+}
+
+/// The dates a COUNT rule counted up to the last day asked for.
+/// The calendar asks for every shown day of every event, so the
+/// periods are expanded once for the shown months, not once a day
+#[derive(Debug, Clone, Default)]
+struct Expansion {
+    /// the start and the rule expanded, starting over when they change
+    source: Option<(NaiveDate, RecurrenceRule)>,
+
+    /// the next period to expand
+    period: u32,
+
+    /// the counted dates, sorted, the exceptions included
+    dates: Vec<NaiveDate>,
+
+    /// whether every date is counted
+    is_done: bool,
+}
+
+impl PartialEq for Recurrence {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.rule == other.rule && self.exceptions == other.exceptions
+    }
+}
+
+impl Eq for Recurrence {}
+
+impl Recurrence {
+
+    /// create a [`Recurrence`] of the rule from the start date
+    pub fn new(start: NaiveDate, rule: RecurrenceRule) -> Self {
+        Self {
+            start,
+            rule,
+            exceptions: Vec::new(),
+            expansion: RefCell::new(Expansion::default()),
+        }
+    }
+
+    /// skip the date
+    pub fn except(mut self, date: NaiveDate) -> Self {
+        self.exceptions.push(date);
+        self
+    }
+
+    /// check if the rule takes place on the date
+    pub fn occurs_on(&self, date: NaiveDate) -> bool {
+        !self.occurrences_between(date, date).is_empty()
+    }
+
+    /// the dates the rule takes place on, from `from` to `to` inclusive.
+    /// the start date is always the first occurrence, and COUNT includes
+    /// the dates skipped by the exceptions
+    pub fn occurrences_between(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let last = match self.rule.until {
+            Some(until) => to.min(until),
+            None => to,
+        };
+
+        if last < self.start || last < from {
+            return Vec::new()
+        }
+        if let Some(count) = self.rule.count {
+            return self.counted_between(count, from, last)
+        }
+
+        // without COUNT, the periods before `from` don't matter
+        let mut occurrences = Vec::new();
+        let mut period = self.period_before(from);
+
+        loop {
+            let (period_start, dates) = match self.period(period) {
+                Some(period) => period,
+                None => return occurrences,
+            };
+            if period_start > last {
+                return occurrences
+            }
+
+            for date in dates {
+                if date > last {
+                    return occurrences
+                }
+                if date >= from && !self.exceptions.contains(&date) {
+                    occurrences.push(date);
+                }
+            }
+
+            period += 1;
+        }
+    }
+
+    /// the occurrences of a COUNT rule from `from` to `last`, counting
+    /// the periods from the first one the first time only
+    fn counted_between(&self, count: u32, from: NaiveDate, last: NaiveDate) -> Vec<NaiveDate> {
+        let mut expansion = self.expansion.borrow_mut();
+
+        match &expansion.source {
+            Some((start, rule)) if *start == self.start && *rule == self.rule => {}
+            _ => {
+                *expansion = Expansion {
+                    source: Some((self.start, self.rule.clone())),
+                    ..Expansion::default()
+                }
+            }
+        }
+
+        while !expansion.is_done {
+            let (period_start, dates) = match self.period(expansion.period) {
+                Some(period) => period,
+                None => {
+                    expansion.is_done = true;
+                    break
+                }
+            };
+            if period_start > last {
+                break
+            }
+
+            let left = count as usize - expansion.dates.len();
+            expansion.dates.extend(dates.into_iter().take(left));
+            expansion.is_done = expansion.dates.len() >= count as usize;
+            expansion.period += 1;
+        }
+
+        let first = expansion.dates.binary_search(&from).unwrap_or_else(|index| index);
+        expansion.dates[first..]
+            .iter()
+            .take_while(|date| **date <= last)
+            .filter(|date| !self.exceptions.contains(date))
+            .copied()
+            .collect()
+    }
+
+    /// the index of the period containing `date`, or 0
+    fn period_before(&self, date: NaiveDate) -> u32 {
+        if date <= self.start {
+            return 0
+        }
+
+        let interval = self.rule.interval.max(1) as i64;
+        let elapsed = match self.rule.frequency {
+            Frequency::Daily => (date - self.start).num_days(),
+            Frequency::Weekly => (week_start(date) - week_start(self.start)).num_weeks(),
+            Frequency::Monthly => {
+                (date.year() - self.start.year()) as i64 * 12
+                    + date.month() as i64 - self.start.month() as i64
+            }
+            Frequency::Yearly => (date.year() - self.start.year()) as i64,
+        };
+
+        (elapsed / interval).max(0) as u32
+    }
+
+    /// the first day of the nth period and its dates from the start date, sorted
+    fn period(&self, n: u32) -> Option<(NaiveDate, Vec<NaiveDate>)> {
+        let rule = &self.rule;
+        let step = n as i64 * rule.interval.max(1) as i64;
+
+        let (period_start, mut dates) = match rule.frequency {
+            Frequency::Daily => {
+                let date = self.start.checked_add_signed(Duration::days(step))?;
+                let matches = (rule.by_day.is_empty()
+                        || rule.by_day.iter().any(|(_, weekday)| *weekday == date.weekday()))
+                    && (rule.by_month_day.is_empty()
+                        || month_days(date.year(), date.month(), &rule.by_month_day).contains(&date));
+
+                (date, if matches { vec![date] } else { Vec::new() })
+            }
+            Frequency::Weekly => {
+                let monday = week_start(self.start).checked_add_signed(Duration::weeks(step))?;
+                let dates = if rule.by_day.is_empty() {
+                    vec![monday + Duration::days(self.start.weekday().num_days_from_monday() as i64)]
+                } else {
+                    rule.by_day
+                        .iter()
+                        .map(|(_, weekday)| monday + Duration::days(weekday.num_days_from_monday() as i64))
+                        .collect()
+                };
+
+                (monday, dates)
+            }
+            Frequency::Monthly => {
+                let (year, month) = add_months(self.start.year(), self.start.month(), step as i32);
+                (NaiveDate::from_ymd_opt(year, month, 1)?, self.month_dates(year, month))
+            }
+            Frequency::Yearly => {
+                let year = self.start.year() + step as i32;
+                (NaiveDate::from_ymd_opt(year, 1, 1)?, self.year_dates(year))
+            }
+        };
+
+        if !rule.by_month.is_empty() {
+            dates.retain(|date| rule.by_month.contains(&date.month()));
+        }
+
+        // the start date counts even when the rule doesn't match it
+        dates.retain(|date| *date >= self.start);
+        if n == 0 {
+            dates.push(self.start);
+        }
+
+        dates.sort();
+        dates.dedup();
+        Some((period_start, dates))
+    }
+
+    /// the candidate dates of a yearly rule in a year
+    fn year_dates(&self, year: i32) -> Vec<NaiveDate> {
+        let rule = &self.rule;
+
+        if !rule.by_month.is_empty() {
+            rule.by_month.iter().flat_map(|month| self.month_dates(year, *month)).collect()
+        } else if !rule.by_month_day.is_empty() {
+            (1..=12).flat_map(|month| self.month_dates(year, month)).collect()
+        } else if !rule.by_day.is_empty() {
+            let mut dates = Vec::new();
+
+            // without months, the positions count in the year: 20MO is its 20th Monday
+            for (n, weekday) in &rule.by_day {
+                match n {
+                    Some(n) => dates.extend(nth_weekday_of_year(year, *weekday, *n)),
+                    None => dates.extend((1..54).filter_map(|n| nth_weekday_of_year(year, *weekday, n))),
+                }
+            }
+
+            dates
+        } else {
+            self.month_dates(year, self.start.month())
+        }
+    }
+
+    /// the candidate dates of a monthly or yearly rule in a month
+    fn month_dates(&self, year: i32, month: u32) -> Vec<NaiveDate> {
+        let rule = &self.rule;
+
+        if !rule.by_month_day.is_empty() {
+            // BYDAY only limits the days of the month
+            month_days(year, month, &rule.by_month_day)
+                .into_iter()
+                .filter(|date| rule.by_day.is_empty()
+                    || rule.by_day.iter().any(|(_, weekday)| *weekday == date.weekday()))
+                .collect()
+        } else if !rule.by_day.is_empty() {
+            let mut dates = Vec::new();
+
+            for (n, weekday) in &rule.by_day {
+                match n {
+                    Some(n) => dates.extend(nth_weekday(year, month, *weekday, *n)),
+                    None => dates.extend((1..6).filter_map(|n| nth_weekday(year, month, *weekday, n))),
+                }
+            }
+
+            dates
+        } else {
+            NaiveDate::from_ymd_opt(year, month, self.start.day()).into_iter().collect()
+        }
+    }
+}
+
+/// the dates of a month matching BYMONTHDAY values
+fn month_days(year: i32, month: u32, days: &[i32]) -> Vec<NaiveDate> {
+    let last = match last_day_of_month(year, month) {
+        Some(last) => last.day() as i32,
+        None => return Vec::new(),
+    };
+
+    days.iter()
+        .map(|day| if *day < 0 { last + day + 1 } else { *day })
+        .filter(|day| *day >= 1 && *day <= last)
+        .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day as u32))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd(year, month, day)
+    }
+
+    fn recurrence(start: NaiveDate, rule: &str) -> Recurrence {
+        Recurrence::new(start, rule.parse().unwrap())
+    }
+
+    #[test]
+    fn read_and_written() {
+        let rule: RecurrenceRule = "RRULE:FREQ=MONTHLY;INTERVAL=2;BYDAY=2TU,-1FR;COUNT=10".parse().unwrap();
+        assert_eq!(rule.frequency, Frequency::Monthly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.by_day, vec![(Some(2), Weekday::Tue), (Some(-1), Weekday::Fri)]);
+        assert_eq!(rule.count, Some(10));
+        assert_eq!(rule.to_string(), "FREQ=MONTHLY;INTERVAL=2;BYDAY=2TU,-1FR;COUNT=10");
+
+        // the time of UNTIL is dropped, WKST is read but always Monday
+        let rule: RecurrenceRule = "freq=yearly;bymonth=3;bymonthday=-1;until=20251231T235959Z;wkst=SU".parse().unwrap();
+        assert_eq!(rule.by_month, vec![3]);
+        assert_eq!(rule.by_month_day, vec![-1]);
+        assert_eq!(rule.until, Some(date(2025, 12, 31)));
+        assert_eq!(rule.to_string(), "FREQ=YEARLY;BYMONTHDAY=-1;BYMONTH=3;UNTIL=20251231");
+    }
+
+    #[test]
+    fn unreadable_rules() {
+        let parse = |rule: &str| rule.parse::<RecurrenceRule>().unwrap_err();
+
+        assert_eq!(parse("INTERVAL=2"), ParseError::MissingFrequency);
+        assert_eq!(parse("FREQ=WEEKLY;INTERVAL=0"), ParseError::InvalidPart(String::from("INTERVAL=0")));
+        assert_eq!(parse("FREQ=WEEKLY;BYDAY=XX"), ParseError::InvalidPart(String::from("BYDAY=XX")));
+        assert_eq!(parse("FREQ=YEARLY;BYMONTH=13"), ParseError::InvalidPart(String::from("BYMONTH=13")));
+        assert_eq!(parse("FREQ=MONTHLY;BYMONTHDAY=0"), ParseError::InvalidPart(String::from("BYMONTHDAY=0")));
+        assert_eq!(parse("FREQ=HOURLY"), ParseError::Unsupported(String::from("FREQ=HOURLY")));
+        assert_eq!(parse("FREQ=MONTHLY;BYSETPOS=-1"), ParseError::Unsupported(String::from("BYSETPOS=-1")));
+    }
+
+    #[test]
+    fn every_other_week() {
+        let recurrence = recurrence(date(2020, 6, 2), "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH");
+
+        assert_eq!(
+            recurrence.occurrences_between(date(2020, 6, 1), date(2020, 6, 30)),
+            vec![date(2020, 6, 2), date(2020, 6, 4), date(2020, 6, 16), date(2020, 6, 18), date(2020, 6, 30)]
+        );
+
+        // from the middle of a period
+        assert_eq!(
+            recurrence.occurrences_between(date(2020, 6, 17), date(2020, 7, 2)),
+            vec![date(2020, 6, 18), date(2020, 6, 30), date(2020, 7, 2)]
+        );
+    }
+
+    #[test]
+    fn until_is_the_last_date() {
+        let recurrence = recurrence(date(2020, 6, 1), "FREQ=WEEKLY;UNTIL=20200615");
+
+        assert_eq!(
+            recurrence.occurrences_between(date(2020, 5, 1), date(2020, 7, 31)),
+            vec![date(2020, 6, 1), date(2020, 6, 8), date(2020, 6, 15)]
+        );
+    }
+
+    #[test]
+    fn count_includes_the_exceptions() {
+        let recurrence = recurrence(date(2020, 6, 1), "FREQ=DAILY;COUNT=5").except(date(2020, 6, 3));
+
+        assert_eq!(
+            recurrence.occurrences_between(date(2020, 6, 1), date(2020, 6, 30)),
+            vec![date(2020, 6, 1), date(2020, 6, 2), date(2020, 6, 4), date(2020, 6, 5)]
+        );
+        assert!(!recurrence.occurs_on(date(2020, 6, 3)));
+    }
+
+    #[test]
+    fn count_asked_day_by_day() {
+        let mut recurrence = recurrence(date(2020, 6, 9), "FREQ=MONTHLY;BYDAY=2TU;COUNT=3");
+
+        // in any order, as the days of the shown months are
+        assert!(recurrence.occurs_on(date(2020, 8, 11)));
+        assert!(!recurrence.occurs_on(date(2020, 9, 8)));
+        assert!(recurrence.occurs_on(date(2020, 7, 14)));
+        assert!(!recurrence.occurs_on(date(2020, 7, 15)));
+
+        // a changed rule is counted again
+        recurrence.rule.count = Some(2);
+        assert!(!recurrence.occurs_on(date(2020, 8, 11)));
+        assert!(recurrence.occurs_on(date(2020, 7, 14)));
+    }
+
+    #[test]
+    fn weekdays_of_the_month() {
+        let second_tuesday = recurrence(date(2020, 6, 9), "FREQ=MONTHLY;BYDAY=2TU");
+        assert_eq!(
+            second_tuesday.occurrences_between(date(2020, 6, 1), date(2020, 8, 31)),
+            vec![date(2020, 6, 9), date(2020, 7, 14), date(2020, 8, 11)]
+        );
+
+        let last_friday = recurrence(date(2020, 6, 26), "FREQ=MONTHLY;BYDAY=-1FR");
+        assert_eq!(
+            last_friday.occurrences_between(date(2020, 6, 1), date(2020, 8, 31)),
+            vec![date(2020, 6, 26), date(2020, 7, 31), date(2020, 8, 28)]
+        );
+    }
+
+    #[test]
+    fn days_from_the_end_of_the_month() {
+        let recurrence = recurrence(date(2020, 1, 31), "FREQ=MONTHLY;BYMONTHDAY=-1");
+
+        assert_eq!(
+            recurrence.occurrences_between(date(2020, 1, 1), date(2020, 4, 30)),
+            vec![date(2020, 1, 31), date(2020, 2, 29), date(2020, 3, 31), date(2020, 4, 30)]
+        );
+    }
+
+    #[test]
+    fn weekdays_of_the_year() {
+        let mondays = recurrence(date(2021, 1, 4), "FREQ=YEARLY;BYDAY=MO");
+        let occurrences = mondays.occurrences_between(date(2021, 1, 1), date(2021, 12, 31));
+        assert_eq!(occurrences.len(), 52);
+        assert_eq!(occurrences.last(), Some(&date(2021, 12, 27)));
+
+        // the start date is the first occurrence, then the 20th Monday of every year
+        let twentieth = recurrence(date(2021, 1, 4), "FREQ=YEARLY;BYDAY=20MO");
+        assert_eq!(
+            twentieth.occurrences_between(date(2021, 1, 1), date(2022, 12, 31)),
+            vec![date(2021, 1, 4), date(2021, 5, 17), date(2022, 5, 16)]
+        );
+    }
+
+    #[test]
+    fn weekdays_of_a_month_of_the_year() {
+        let recurrence = recurrence(date(2021, 3, 28), "FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU");
+
+        assert_eq!(
+            recurrence.occurrences_between(date(2021, 1, 1), date(2023, 12, 31)),
+            vec![date(2021, 3, 28), date(2022, 3, 27), date(2023, 3, 26)]
+        );
+    }
+
+    #[test]
+    fn days_of_every_month_of_the_year() {
+        let recurrence = recurrence(date(2021, 1, 15), "FREQ=YEARLY;BYMONTHDAY=15");
+
+        assert_eq!(recurrence.occurrences_between(date(2021, 1, 1), date(2021, 12, 31)).len(), 12);
+    }
+}
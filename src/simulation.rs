@@ -0,0 +1,477 @@
+///
+/// Simulated interactions with a DatePicker: clicks at semantic targets
+/// and synthetic events, giving the messages and the resulting state
+///
+use std::marker::PhantomData;
+
+use iced_graphics::Renderer;
+use iced_native::{layout, mouse, Cache, Event, Point, Size, UserInterface, Widget};
+
+use crate::date_picker::{DatePicker, State, Target};
+use crate::headless::Headless;
+
+/// Builds a [`DatePicker`] of its [`State`] again for every event,
+/// as the view of a host does
+pub struct Simulation<Message, F> {
+    state: State,
+    build: F,
+    size: Size,
+    renderer: Renderer<Headless>,
+    message: PhantomData<Message>,
+}
+
+impl<Message, F> Simulation<Message, F>
+where
+    Message: Clone,
+    F: for<'a> Fn(&'a mut State) -> DatePicker<'a, Message>,
+{
+    /// create a [`Simulation`] of the pickers built of the state,
+    /// laid out within the size
+    pub fn new(state: State, size: Size, build: F) -> Self {
+        Self {
+            state,
+            build,
+            size,
+            renderer: Renderer::new(Headless),
+            message: PhantomData,
+        }
+    }
+
+    /// the resulting state
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// the state, to change it as the host would between events,
+    /// e.g. opening the calendar at once
+    pub fn state_mut(&mut self) -> &mut State {
+        &mut self.state
+    }
+
+    /// where a click reaches the target, none when it isn't shown
+    pub fn position(&mut self, target: Target) -> Option<Point> {
+        let picker = (self.build)(&mut self.state);
+        let root = Widget::<Message, Renderer<Headless>>::layout(
+            &picker,
+            &self.renderer,
+            &layout::Limits::new(Size::ZERO, self.size),
+        );
+
+        picker.position(&root, target)
+    }
+
+    /// Presses and releases the left button on the target,
+    /// giving the messages produced.
+    ///
+    /// Panics if the target isn't shown, e.g. a day of a closed calendar
+    pub fn click(&mut self, target: Target) -> Vec<Message> {
+        let position = self
+            .position(target)
+            .unwrap_or_else(|| panic!("{:?} isn't shown", target));
+
+        self.events(
+            &[
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)),
+                Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)),
+            ],
+            position,
+        )
+    }
+
+    /// Feeds the mouse or keyboard events with the cursor at the position,
+    /// giving the messages produced
+    pub fn events(&mut self, events: &[Event], cursor_position: Point) -> Vec<Message> {
+        let picker = (self.build)(&mut self.state);
+        let mut user_interface = UserInterface::build(picker, self.size, Cache::new(), &mut self.renderer);
+
+        user_interface.update(events.iter().cloned(), cursor_position, None, &self.renderer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::prelude::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::{Duration, Instant};
+    use iced_native::keyboard;
+
+    use crate::date_picker::{Animation, Selection};
+    use crate::headless;
+    use crate::presets::Preset;
+    use crate::snapshot::serialize;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Message {
+        Change(String),
+        Select(Option<NaiveDate>),
+        Open,
+        Close,
+        Cancel,
+        Preset(Selection),
+        MonthChange(i32, u32),
+        Hover(NaiveDate),
+        AnimationStart,
+    }
+
+    /// a simulation of a picker sending every message, its calendar closed
+    fn simulation(
+        date: NaiveDate,
+        close_on_select: bool,
+    ) -> Simulation<Message, impl for<'a> Fn(&'a mut State) -> DatePicker<'a, Message>> {
+        Simulation::new(State::with_date(date), Size::new(1000.0, 1000.0), move |state| {
+            DatePicker::new(350, state, "", "", Message::Change)
+                .nullable(Message::Select)
+                .on_open(Message::Open)
+                .on_close(Message::Close)
+                .on_cancel(Message::Cancel)
+                .close_on_select(close_on_select)
+                .today(NaiveDate::from_ymd(2020, 6, 5))
+        })
+    }
+
+    fn key(key_code: keyboard::KeyCode) -> Event {
+        Event::Keyboard(keyboard::Event::KeyPressed {
+            key_code,
+            modifiers: keyboard::ModifiersState::default(),
+        })
+    }
+
+    fn typed(text: &str) -> Vec<Event> {
+        text.chars().map(|c| Event::Keyboard(keyboard::Event::CharacterReceived(c))).collect()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd(year, month, day)
+    }
+
+    #[test]
+    fn click_a_day() {
+        let mut simulation = simulation(date(2020, 6, 5), true);
+
+        assert_eq!(simulation.click(Target::Input), vec![Message::Open]);
+        assert!(simulation.state().is_focused);
+
+        assert_eq!(
+            simulation.click(Target::Day(14)),
+            vec![
+                Message::Change(String::from("2020-06-14")),
+                Message::Select(Some(date(2020, 6, 14))),
+                Message::Close,
+            ],
+        );
+        assert!(!simulation.state().is_focused);
+        assert_eq!(simulation.state().selection(), Some(Selection::Single(date(2020, 6, 14))));
+    }
+
+    #[test]
+    fn click_a_day_staying_open() {
+        let mut simulation = simulation(date(2020, 6, 5), false);
+        simulation.click(Target::Input);
+
+        assert_eq!(
+            simulation.click(Target::Day(14)),
+            vec![Message::Change(String::from("2020-06-14")), Message::Select(Some(date(2020, 6, 14)))],
+        );
+        assert!(simulation.state().is_focused);
+    }
+
+    #[test]
+    fn click_a_day_of_the_second_month() {
+        let mut simulation = Simulation::new(State::with_date(date(2020, 6, 5)), Size::new(1000.0, 1000.0), |state| {
+            DatePicker::new(700, state, "", "", Message::Change).months(2).close_on_select(false)
+        });
+        simulation.click(Target::Input);
+        let june = simulation.position(Target::Date(date(2020, 6, 5)));
+
+        assert_eq!(simulation.click(Target::Date(date(2020, 7, 14))), vec![Message::Change(String::from("2020-07-14"))]);
+        assert_eq!(simulation.state().date(), date(2020, 7, 14));
+
+        // June is still the first month shown
+        assert!(june.is_some());
+        assert_eq!(simulation.position(Target::Date(date(2020, 6, 5))), june);
+    }
+
+    #[test]
+    fn next_keeps_the_day_within_the_month() {
+        let mut simulation = simulation(date(2021, 1, 31), true);
+        simulation.click(Target::Input);
+
+        // navigating sends nothing
+        assert!(simulation.click(Target::Next).is_empty());
+        assert_eq!(simulation.state().date(), date(2021, 2, 28));
+
+        assert!(simulation.click(Target::Next).is_empty());
+        assert_eq!(simulation.state().date(), date(2021, 3, 28));
+        assert!(simulation.state().is_focused);
+    }
+
+    #[test]
+    fn click_outside_closes() {
+        let mut simulation = simulation(date(2020, 6, 5), true);
+        simulation.click(Target::Input);
+
+        assert_eq!(simulation.click(Target::Outside), vec![Message::Cancel, Message::Close]);
+        assert!(!simulation.state().is_focused);
+        assert_eq!(simulation.state().date(), date(2020, 6, 5));
+
+        // closed, another click outside does nothing
+        assert!(simulation.click(Target::Outside).is_empty());
+    }
+
+    #[test]
+    fn escape_cancels() {
+        let mut simulation = simulation(date(2020, 6, 5), true);
+        simulation.click(Target::Input);
+        simulation.click(Target::Next);
+
+        let escape = key(keyboard::KeyCode::Escape);
+        assert_eq!(simulation.events(&[escape], Point::new(-1.0, -1.0)), vec![Message::Cancel, Message::Close]);
+        assert!(!simulation.state().is_focused);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn on_focus_is_not_sent() {
+        let mut simulation = Simulation::new(State::with_date(date(2020, 6, 5)), Size::new(1000.0, 1000.0), |state| {
+            DatePicker::new(350, state, "", "", Message::Change)
+                .on_focus(Message::Open)
+                .on_close(Message::Close)
+        });
+
+        // a host toggling the state on it would close the calendar again
+        assert!(simulation.click(Target::Input).is_empty());
+        assert!(simulation.state().is_focused);
+        assert_eq!(simulation.click(Target::Input), vec![Message::Close]);
+    }
+
+    #[test]
+    fn preset_sends_its_last_day_then_its_selection() {
+        let mut simulation = Simulation::new(State::with_date(date(2020, 6, 5)), Size::new(1000.0, 1000.0), |state| {
+            DatePicker::new(350, state, "", "", Message::Change)
+                .nullable(Message::Select)
+                .on_close(Message::Close)
+                .today(NaiveDate::from_ymd(2020, 6, 12))
+                .presets(vec![Preset::Yesterday, Preset::LastDays(7)], Message::Preset)
+        });
+        simulation.state_mut().is_focused = true;
+
+        assert_eq!(
+            simulation.click(Target::Preset(Preset::LastDays(7))),
+            vec![
+                Message::Change(String::from("2020-06-12")),
+                Message::Select(Some(date(2020, 6, 12))),
+                Message::Preset(Selection::Range(date(2020, 6, 6), date(2020, 6, 12))),
+                Message::Close,
+            ],
+        );
+        assert_eq!(simulation.state().selection(), Some(Selection::Range(date(2020, 6, 6), date(2020, 6, 12))));
+
+        simulation.state_mut().is_focused = true;
+        assert_eq!(
+            simulation.click(Target::Preset(Preset::Yesterday)),
+            vec![
+                Message::Change(String::from("2020-06-11")),
+                Message::Select(Some(date(2020, 6, 11))),
+                Message::Preset(Selection::Single(date(2020, 6, 11))),
+                Message::Close,
+            ],
+        );
+    }
+
+    #[test]
+    fn clear_input() {
+        let mut simulation = simulation(date(2020, 6, 5), true);
+
+        assert_eq!(simulation.click(Target::ClearInput), vec![Message::Select(None)]);
+        assert_eq!(simulation.state().value(), None);
+        assert_eq!(simulation.state().selection(), None);
+        assert!(!simulation.state().is_focused);
+
+        // nothing to clear anymore
+        assert_eq!(simulation.position(Target::ClearInput), None);
+    }
+
+    #[test]
+    fn clear_in_the_footer() {
+        let mut simulation = simulation(date(2020, 6, 5), true);
+        simulation.click(Target::Input);
+
+        assert_eq!(simulation.click(Target::Clear), vec![Message::Select(None), Message::Close]);
+        assert_eq!(simulation.state().value(), None);
+        assert_eq!(simulation.state().selection(), None);
+
+        // the month stays, and a day can be picked again
+        simulation.click(Target::Input);
+        assert_eq!(
+            simulation.click(Target::Day(14)),
+            vec![
+                Message::Change(String::from("2020-06-14")),
+                Message::Select(Some(date(2020, 6, 14))),
+                Message::Close,
+            ],
+        );
+        assert_eq!(simulation.state().value(), Some(date(2020, 6, 14)));
+    }
+
+    #[test]
+    fn typed_date_is_only_previewed() {
+        let mut simulation = simulation(date(2020, 6, 5), true);
+        let away = Point::new(-1.0, -1.0);
+        simulation.click(Target::Input);
+
+        assert!(simulation.events(&typed("tomorrow"), away).is_empty());
+        assert_eq!(simulation.state().selection(), Some(Selection::Single(date(2020, 6, 5))));
+
+        assert_eq!(simulation.events(&[key(keyboard::KeyCode::Escape)], away), vec![Message::Cancel, Message::Close]);
+        assert_eq!(simulation.state().selection(), Some(Selection::Single(date(2020, 6, 5))));
+
+        // a month later, the grid shows July until Escape
+        simulation.click(Target::Input);
+        simulation.events(&typed("+1m"), away);
+        assert_eq!(simulation.state().value(), Some(date(2020, 6, 5)));
+        assert!(simulation.position(Target::Date(date(2020, 7, 5))).is_some());
+
+        simulation.events(&[key(keyboard::KeyCode::Escape)], away);
+        simulation.click(Target::Input);
+        assert!(simulation.position(Target::Date(date(2020, 7, 5))).is_none());
+        assert_eq!(simulation.click(Target::Day(14))[0], Message::Change(String::from("2020-06-14")));
+    }
+
+    #[test]
+    fn unknown_draft_shows_the_selection_again() {
+        let mut simulation = simulation(date(2020, 6, 5), true);
+        let away = Point::new(-1.0, -1.0);
+        simulation.click(Target::Input);
+
+        simulation.events(&typed("next fri"), away);
+        simulation.events(&typed("d"), away);
+        assert_eq!(simulation.state().selection(), Some(Selection::Single(date(2020, 6, 5))));
+
+        // nothing to confirm
+        assert!(simulation.events(&[key(keyboard::KeyCode::Enter)], away).is_empty());
+        assert!(simulation.state().is_focused);
+
+        simulation.events(&[key(keyboard::KeyCode::Backspace)], away);
+        assert_eq!(
+            simulation.events(&[key(keyboard::KeyCode::Enter)], away),
+            vec![
+                Message::Change(String::from("2020-06-12")),
+                Message::Select(Some(date(2020, 6, 12))),
+                Message::Close,
+            ],
+        );
+        assert_eq!(simulation.state().selection(), Some(Selection::Single(date(2020, 6, 12))));
+    }
+
+    /// a simulation of a picker controlled by the date of the host, opened
+    fn controlled(
+        host: &Rc<Cell<NaiveDate>>,
+    ) -> Simulation<Message, impl for<'a> Fn(&'a mut State) -> DatePicker<'a, Message>> {
+        let host = Rc::clone(host);
+        let mut simulation = Simulation::new(State::new(), Size::new(1000.0, 1000.0), move |state| {
+            DatePicker::new(350, state, "", "", Message::Change).date(host.get())
+        });
+        simulation.click(Target::Input);
+
+        simulation
+    }
+
+    #[test]
+    fn host_changes_the_date() {
+        let host = Rc::new(Cell::new(date(2020, 6, 5)));
+        let mut simulation = controlled(&host);
+        assert_eq!(simulation.state().date(), date(2020, 6, 5));
+
+        // e.g. another record loaded, shown on the next view
+        host.set(date(2020, 9, 10));
+        assert!(simulation.events(&[], Point::new(-1.0, -1.0)).is_empty());
+        assert_eq!(simulation.state().value(), Some(date(2020, 9, 10)));
+        assert_eq!(simulation.click(Target::Day(14)), vec![Message::Change(String::from("2020-09-14"))]);
+    }
+
+    #[test]
+    fn navigating_keeps_the_committed_date() {
+        let mut simulation = controlled(&Rc::new(Cell::new(date(2020, 6, 5))));
+
+        assert!(simulation.click(Target::Next).is_empty());
+        assert!(simulation.click(Target::Next).is_empty());
+        assert_eq!(simulation.state().date(), date(2020, 6, 5));
+        assert!(simulation.position(Target::Date(date(2020, 8, 5))).is_some());
+
+        // the footer shows the committed date too
+        let mut state = simulation.state().clone();
+        let picker = DatePicker::new(350, &mut state, "", "", Message::Change).date(date(2020, 6, 5));
+        let (primitive, _) = headless::draw(picker, Size::new(1000.0, 1000.0));
+        let drawn = serialize(&primitive);
+        assert!(drawn.contains("text \"2020-06-05\""));
+        assert!(!drawn.contains("text \"2020-08-05\""));
+    }
+
+    #[test]
+    fn host_ignoring_a_pick_reverts_it() {
+        let mut simulation = controlled(&Rc::new(Cell::new(date(2020, 6, 5))));
+
+        assert_eq!(simulation.click(Target::Day(14)), vec![Message::Change(String::from("2020-06-14"))]);
+        assert_eq!(simulation.state().date(), date(2020, 6, 14));
+
+        // the host kept its date, shown again on the next view
+        simulation.events(&[], Point::new(-1.0, -1.0));
+        assert_eq!(simulation.state().date(), date(2020, 6, 5));
+        assert_eq!(simulation.state().selection(), Some(Selection::Single(date(2020, 6, 5))));
+    }
+
+    /// a simulation of an open picker sending its first shown month and the hovered day
+    fn watched(date: NaiveDate) -> Simulation<Message, impl for<'a> Fn(&'a mut State) -> DatePicker<'a, Message>> {
+        let mut simulation = Simulation::new(State::with_date(date), Size::new(1000.0, 1000.0), |state| {
+            DatePicker::new(350, state, "", "", Message::Change)
+                .on_month_change(Message::MonthChange)
+                .on_hover(Message::Hover)
+        });
+        simulation.click(Target::Input);
+
+        simulation
+    }
+
+    #[test]
+    fn next_sends_the_new_first_month() {
+        let mut simulation = watched(date(2020, 12, 5));
+
+        assert_eq!(simulation.click(Target::Next), vec![Message::MonthChange(2021, 1)]);
+        assert_eq!(simulation.click(Target::Previous), vec![Message::MonthChange(2020, 12)]);
+    }
+
+    #[test]
+    fn hovering_a_day_sends_it() {
+        let mut simulation = watched(date(2020, 6, 5));
+        let position = simulation.position(Target::Day(14)).unwrap();
+        let moved = || Event::Mouse(mouse::Event::CursorMoved { x: position.x, y: position.y });
+
+        assert_eq!(simulation.events(&[moved()], position), vec![Message::Hover(date(2020, 6, 14))]);
+
+        // moving within the day sends nothing new
+        assert!(simulation.events(&[moved()], position).is_empty());
+    }
+
+    #[test]
+    fn starting_an_animation_is_sent() {
+        let mut state = State::with_date(date(2020, 6, 5));
+        state.animation = Animation::Slide;
+        let mut simulation = Simulation::new(state, Size::new(1000.0, 1000.0), |state| {
+            DatePicker::new(350, state, "", "", Message::Change).on_animation_start(Message::AnimationStart)
+        });
+
+        assert_eq!(simulation.click(Target::Input), vec![Message::AnimationStart]);
+        let now = Instant::now();
+        simulation.state_mut().tick(now);
+
+        // once only while it runs, the months starting at the next tick
+        assert_eq!(simulation.click(Target::Next), Vec::new());
+        simulation.state_mut().tick(now + Duration::from_secs(1));
+        simulation.state_mut().tick(now + Duration::from_secs(2));
+        assert!(!simulation.state().is_animating());
+
+        assert_eq!(simulation.click(Target::Next), vec![Message::AnimationStart]);
+    }
+}
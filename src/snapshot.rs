@@ -0,0 +1,324 @@
+///
+/// Snapshots of the DatePicker: its primitives as stable text,
+/// compared with the golden files of known states and styles
+///
+use std::fmt::Write;
+
+use iced_graphics::Primitive;
+use iced_native::{Background, Color, Font};
+
+/// Writes the primitive tree as indented lines, one per primitive,
+/// the numbers rounded so that the text is stable
+pub fn serialize(primitive: &Primitive) -> String {
+    let mut out = String::new();
+    write_primitive(&mut out, primitive, 0);
+    out
+}
+
+/// the number with two decimals at most, e.g. "57.14"
+fn number(value: f32) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+
+    // no "-0"
+    format!("{}", if rounded == 0.0 { 0.0 } else { rounded })
+}
+
+/// the color as "#rrggbbaa"
+fn color(color: Color) -> String {
+    let channel = |c: f32| (c.max(0.0).min(1.0) * 255.0).round() as u8;
+
+    format!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b),
+        channel(color.a),
+    )
+}
+
+fn font(font: Font) -> &'static str {
+    match font {
+        Font::Default => "default",
+        Font::External { name, .. } => name,
+    }
+}
+
+fn write_primitive(out: &mut String, primitive: &Primitive, depth: usize) {
+    let indent = "  ".repeat(depth);
+
+    let _ = match primitive {
+        Primitive::None => writeln!(out, "{}none", indent),
+        Primitive::Group { primitives } => {
+            let _ = writeln!(out, "{}group", indent);
+            for primitive in primitives {
+                write_primitive(out, primitive, depth + 1);
+            }
+            Ok(())
+        }
+        Primitive::Quad { bounds, background, border_radius, border_width, border_color } => {
+            let Background::Color(background) = background;
+
+            writeln!(
+                out,
+                "{}quad {} {} {} {} background {} border {} {} radius {}",
+                indent,
+                number(bounds.x),
+                number(bounds.y),
+                number(bounds.width),
+                number(bounds.height),
+                color(*background),
+                border_width,
+                color(*border_color),
+                border_radius,
+            )
+        }
+        Primitive::Text {
+            content,
+            bounds,
+            color: text_color,
+            size,
+            font: text_font,
+            horizontal_alignment,
+            vertical_alignment,
+        } => writeln!(
+            out,
+            "{}text {:?} {} {} {} {} size {} font {} color {} align {:?} {:?}",
+            indent,
+            content,
+            number(bounds.x),
+            number(bounds.y),
+            number(bounds.width),
+            number(bounds.height),
+            number(*size),
+            font(*text_font),
+            color(*text_color),
+            horizontal_alignment,
+            vertical_alignment,
+        ),
+        Primitive::Clip { bounds, offset, content } => {
+            let _ = writeln!(
+                out,
+                "{}clip {} {} {} {} offset {} {}",
+                indent,
+                number(bounds.x),
+                number(bounds.y),
+                number(bounds.width),
+                number(bounds.height),
+                offset.x,
+                offset.y,
+            );
+            write_primitive(out, content, depth + 1);
+            Ok(())
+        }
+        Primitive::Translate { translation, content } => {
+            let _ = writeln!(out, "{}translate {} {}", indent, number(translation.x), number(translation.y));
+            write_primitive(out, content, depth + 1);
+            Ok(())
+        }
+        Primitive::Cached { cache } => {
+            write_primitive(out, cache, depth);
+            Ok(())
+        }
+
+        // the picker draws no images nor meshes
+        _ => writeln!(out, "{}other", indent),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+
+    use iced_native::{keyboard, Event, Length, Point, Size};
+
+    use chrono::prelude::*;
+
+    use crate::date_picker::{DatePicker, State, Target};
+    use crate::headless;
+    use crate::holiday::{Holiday, HolidayProvider};
+    use crate::presets::Preset;
+    use crate::simulation::Simulation;
+
+    /// the room given to the pickers
+    const SIZE: Size = Size { width: 1000.0, height: 1000.0 };
+
+    /// the value of the input in every case
+    const VALUE: &str = "2020-06-05";
+
+    /// the style of a case, applied to the default picker
+    type Style = for<'a> fn(DatePicker<'a, ()>) -> DatePicker<'a, ()>;
+
+    /// What the user does before a case is drawn
+    #[derive(Debug, Clone, Copy)]
+    enum Step {
+        Click(Target),
+        Type(&'static str),
+    }
+
+    /// Flag Day on 14 June, the holiday of the hovered case
+    struct FlagDay;
+
+    impl HolidayProvider for FlagDay {
+        fn holidays(&self, _year: i32, month: u32) -> Vec<Holiday> {
+            match month {
+                6 => vec![Holiday { date: NaiveDate::from_ymd(2020, 6, 14), name: String::from("Flag Day") }],
+                _ => Vec::new(),
+            }
+        }
+    }
+
+    /// A known state and style of the [`DatePicker`]
+    struct Case {
+        name: &'static str,
+        date: NaiveDate,
+        is_focused: bool,
+        style: Style,
+        steps: &'static [Step],
+
+        /// the day drawn as today
+        today: NaiveDate,
+
+        /// where the cursor is when drawn, away from the picker by default
+        cursor: Option<Target>,
+    }
+
+    /// the cases with a golden file, today far from their months
+    /// unless a case shows it
+    fn cases() -> Vec<Case> {
+        let far = NaiveDate::from_ymd(2000, 1, 1);
+        let case = |name, date, is_focused, style| Case {
+            name, date, is_focused, style, steps: &[], today: far, cursor: None,
+        };
+        let june = NaiveDate::from_ymd(2020, 6, 5);
+
+        vec![
+            case("unfocused", june, false, |picker| picker),
+            case("june_2020", june, true, |picker| picker),
+
+            // starts on a Monday and spans four weeks only
+            case("february_2021", NaiveDate::from_ymd(2021, 2, 14), true, |picker| picker),
+            case("two_months", NaiveDate::from_ymd(2020, 12, 31), true, |picker| picker.months(2)),
+            case("scrolling", june, true, |picker| picker.scrolling(2)),
+            case("fixed_size", june, true, |picker| {
+                picker.width(Length::Units(500)).height(Length::Units(400))
+            }),
+            case("text_sizes", june, true, |picker| {
+                picker.header_size(12).day_size(18).footer_size(14)
+            }),
+            case("disabled_weekends", june, true, |picker| {
+                picker.disabled(|date| date.weekday().number_from_monday() > 5)
+            }),
+
+            // the name of the hovered holiday as a tooltip
+            Case {
+                cursor: Some(Target::Day(14)),
+                ..case("hovered", june, true, |picker| picker.holidays(&FlagDay))
+            },
+            Case {
+                steps: &[Step::Click(Target::Day(14))],
+                ..case("selected", june, true, |picker| picker.close_on_select(false))
+            },
+
+            // the draft in the input, its date highlighted
+            Case {
+                steps: &[Step::Type("next friday")],
+                today: june,
+                ..case("keyboard_draft", june, true, |picker| picker)
+            },
+            Case {
+                steps: &[Step::Click(Target::Preset(Preset::LastDays(7)))],
+                today: NaiveDate::from_ymd(2020, 6, 12),
+                ..case("range", june, true, |picker| {
+                    picker
+                        .presets(vec![Preset::LastDays(7)], |_| ())
+                        .close_on_select(false)
+                })
+            },
+        ]
+    }
+
+    /// the case drawn after its steps
+    fn draw(case: &Case) -> String {
+        let mut state = State::with_date(case.date);
+        state.is_focused = case.is_focused;
+
+        let (style, today) = (case.style, case.today);
+        let mut simulation = Simulation::new(state, SIZE, |state| {
+            style(DatePicker::new(350, state, "Choose a date...", VALUE, |_| ()).today(today))
+        });
+
+        for step in case.steps {
+            match *step {
+                Step::Click(target) => {
+                    simulation.click(target);
+                }
+                Step::Type(text) => {
+                    let keys: Vec<Event> = text
+                        .chars()
+                        .map(|c| Event::Keyboard(keyboard::Event::CharacterReceived(c)))
+                        .collect();
+                    simulation.events(&keys, Point::new(-1.0, -1.0));
+                }
+            }
+        }
+
+        let cursor = match case.cursor {
+            Some(target) => simulation.position(target).expect("the hovered target is shown"),
+            None => Point::new(-1.0, -1.0),
+        };
+
+        let mut state = simulation.state().clone();
+        let picker = style(DatePicker::new(350, &mut state, "Choose a date...", VALUE, |_| ()).today(today));
+        let (primitive, _) = headless::draw_at(picker, SIZE, cursor);
+        serialize(&primitive)
+    }
+
+    /// Draws every case and compares it with its golden file in `snapshots/`.
+    /// With `UPDATE_SNAPSHOTS=1`, the files are written instead
+    #[test]
+    fn snapshots() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots");
+        let update = env::var("UPDATE_SNAPSHOTS").ok().as_deref() == Some("1");
+        let mut failures = Vec::new();
+
+        for case in cases() {
+            let actual = draw(&case);
+            let path = directory.join(format!("{}.snap", case.name));
+
+            if update {
+                fs::create_dir_all(&directory)
+                    .and_then(|_| fs::write(&path, &actual))
+                    .unwrap_or_else(|error| panic!("{}: {}", path.display(), error));
+                continue
+            }
+
+            match fs::read_to_string(&path) {
+                Ok(expected) if expected == actual => {}
+                Ok(expected) => {
+                    let line = expected
+                        .lines()
+                        .zip(actual.lines())
+                        .position(|(expected, actual)| expected != actual)
+                        .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+
+                    eprintln!("{}: differs from line {}", path.display(), line + 1);
+                    failures.push(case.name);
+                }
+                Err(error) => {
+                    eprintln!("{}: {}", path.display(), error);
+                    failures.push(case.name);
+                }
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "{} snapshot(s) differ: {}, run with UPDATE_SNAPSHOTS=1 to write them",
+            failures.len(),
+            failures.join(", "),
+        );
+    }
+}
@@ -0,0 +1,10 @@
+///
+/// The palette shared by the DatePicker and the views around it
+///
+use iced_native::Color;
+
+pub(crate) const HIGH_LIGHT_COLOR: Color = Color{r: 118.0/255.0, g: 179.0/225.0, b: 175.0/255.0, a: 1.0};
+pub(crate) const BACKGROUND_COLOR: Color = Color{r: 241.0/255.0, g: 241.0/255.0, b: 241.0/255.0, a: 1.0};
+pub(crate) const BORDER_COLOR: Color = Color{r: 0.0/255.0, g: 0.0/255.0, b: 0.0/255.0, a: 1.0};
+pub(crate) const FIRST_TEXT_COLOR: Color = Color{r: 0.0/255.0, g: 0.0/255.0, b: 0.0/255.0, a: 1.0};
+pub(crate) const SECOND_TEXT_COLOR: Color = Color{r: 200.0/255.0, g: 200.0/255.0, b: 200.0/255.0, a: 1.0};
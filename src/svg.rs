@@ -0,0 +1,217 @@
+///
+/// SVG export: a DatePicker drawn without a window, as a static document
+///
+use std::fmt::Write;
+
+use iced_graphics::Primitive;
+use iced_native::{Background, Color, Font, HorizontalAlignment, Size, VerticalAlignment};
+
+use crate::date_picker::DatePicker;
+use crate::headless;
+
+/// Renders the [`DatePicker`] as configured, with its month, selection
+/// and style, to an SVG document of its layout within the given size.
+///
+/// The primitives are the ones `draw` builds, the calendar being
+/// included when the state is focused.
+pub fn render<Message>(picker: DatePicker<'_, Message>, size: Size) -> String
+where
+    Message: Clone,
+{
+    let (primitive, size) = headless::draw(picker, size);
+    to_svg(&primitive, size)
+}
+
+/// Writes the primitives to an SVG document of the given size
+pub fn to_svg(primitive: &Primitive, size: Size) -> String {
+    let mut body = String::new();
+    let mut clips = 0;
+    write_primitive(&mut body, primitive, &mut clips);
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n{}</svg>\n",
+        body,
+        w = size.width,
+        h = size.height,
+    )
+}
+
+/// the attributes filling or stroking with the color, e.g.
+/// `fill="rgb(241,241,241)" fill-opacity="1"`
+fn paint(attribute: &str, color: Color) -> String {
+    let channel = |c: f32| (c.max(0.0).min(1.0) * 255.0).round() as u8;
+
+    format!(
+        "{a}=\"rgb({},{},{})\" {a}-opacity=\"{}\"",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b),
+        color.a,
+        a = attribute,
+    )
+}
+
+/// escapes the text for an XML document
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// appends the primitive to the document, numbering its clips
+fn write_primitive(out: &mut String, primitive: &Primitive, clips: &mut usize) {
+    match primitive {
+        Primitive::Group { primitives } => {
+            for primitive in primitives {
+                write_primitive(out, primitive, clips);
+            }
+        }
+        Primitive::Quad { bounds, background, border_radius, border_width, border_color } => {
+            let Background::Color(color) = background;
+
+            let _ = writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" {} {} stroke-width=\"{}\"/>",
+                bounds.x,
+                bounds.y,
+                bounds.width,
+                bounds.height,
+                f32::from(*border_radius),
+                paint("fill", *color),
+                paint("stroke", *border_color),
+                f32::from(*border_width),
+            );
+        }
+        Primitive::Text {
+            content,
+            bounds,
+            color,
+            size,
+            font,
+            horizontal_alignment,
+            vertical_alignment,
+        } => {
+            // the bounds start at the point the text is aligned on
+            let anchor = match horizontal_alignment {
+                HorizontalAlignment::Left => "start",
+                HorizontalAlignment::Center => "middle",
+                HorizontalAlignment::Right => "end",
+            };
+            let baseline = match vertical_alignment {
+                VerticalAlignment::Top => "text-before-edge",
+                VerticalAlignment::Center => "central",
+                VerticalAlignment::Bottom => "text-after-edge",
+            };
+            let family = match font {
+                Font::Default => "sans-serif",
+                Font::External { name, .. } => name,
+            };
+
+            let _ = writeln!(
+                out,
+                "<text x=\"{}\" y=\"{}\" font-family=\"{}\" font-size=\"{}\" text-anchor=\"{}\" dominant-baseline=\"{}\" {}>{}</text>",
+                bounds.x,
+                bounds.y,
+                escape(family),
+                size,
+                anchor,
+                baseline,
+                paint("fill", *color),
+                escape(content),
+            );
+        }
+        Primitive::Clip { bounds, offset, content } => {
+            *clips += 1;
+            let id = *clips;
+
+            // the content moves by the offset, the clip stays
+            let _ = writeln!(
+                out,
+                "<clipPath id=\"clip{}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/></clipPath>",
+                id, bounds.x, bounds.y, bounds.width, bounds.height,
+            );
+            let _ = writeln!(
+                out,
+                "<g clip-path=\"url(#clip{})\"><g transform=\"translate({} {})\">",
+                id, -(offset.x as f32), -(offset.y as f32),
+            );
+            write_primitive(out, content, clips);
+            out.push_str("</g></g>\n");
+        }
+        Primitive::Translate { translation, content } => {
+            let _ = writeln!(out, "<g transform=\"translate({} {})\">", translation.x, translation.y);
+            write_primitive(out, content, clips);
+            out.push_str("</g>\n");
+        }
+        Primitive::Cached { cache } => write_primitive(out, cache, clips),
+
+        // the picker draws no images nor meshes
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use iced_native::{Point, Rectangle};
+
+    use crate::date_picker::State;
+
+    fn june(is_focused: bool) -> String {
+        let mut state = State::with_date(NaiveDate::from_ymd(2020, 6, 5));
+        state.is_focused = is_focused;
+
+        let picker = DatePicker::new(350, &mut state, "Choose a date...", "2020-06-05", |_| ());
+        render(picker, Size::new(1000.0, 1000.0))
+    }
+
+    #[test]
+    fn unfocused_picker() {
+        let expected = concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"350\" height=\"40\" viewBox=\"0 0 350 40\">\n",
+            "<rect x=\"0\" y=\"0\" width=\"350\" height=\"40\" rx=\"0\" fill=\"rgb(255,255,255)\" fill-opacity=\"1\" stroke=\"rgb(0,0,0)\" stroke-opacity=\"0\" stroke-width=\"0\"/>\n",
+            "<rect x=\"0\" y=\"0\" width=\"350\" height=\"40\" rx=\"5\" fill=\"rgb(255,255,255)\" fill-opacity=\"1\" stroke=\"rgb(0,0,0)\" stroke-opacity=\"1\" stroke-width=\"1\"/>\n",
+            "<text x=\"10\" y=\"20\" font-family=\"sans-serif\" font-size=\"20\" text-anchor=\"start\" dominant-baseline=\"central\" fill=\"rgb(0,0,0)\" fill-opacity=\"1\">2020-06-05</text>\n",
+            "</svg>\n",
+        );
+
+        assert_eq!(june(false), expected);
+    }
+
+    #[test]
+    fn focused_picker_has_its_calendar() {
+        let svg = june(true);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"350\" height=\"440\" viewBox=\"0 0 350 440\">\n"));
+        assert!(svg.ends_with("</svg>\n"));
+
+        // Friday the 5th selected, the month ending on Tuesday the 30th
+        assert!(svg.contains("<rect x=\"200\" y=\"90\" width=\"50\" height=\"50\" rx=\"0\" fill=\"rgb(118,203,175)\""));
+        assert!(svg.contains("<text x=\"75\" y=\"315\" font-family=\"sans-serif\" font-size=\"31.5\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"rgb(0,0,0)\" fill-opacity=\"1\">30</text>"));
+        assert!(!svg.contains(">31</text>"));
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        let text = Primitive::Text {
+            content: String::from("<Tom & \"Jerry\">"),
+            bounds: Rectangle::new(Point::new(0.0, 10.0), Size::new(100.0, 20.0)),
+            color: Color::BLACK,
+            size: 20.0,
+            font: Font::Default,
+            horizontal_alignment: HorizontalAlignment::Right,
+            vertical_alignment: VerticalAlignment::Bottom,
+        };
+
+        assert_eq!(
+            to_svg(&text, Size::new(100.0, 30.0)),
+            concat!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"30\" viewBox=\"0 0 100 30\">\n",
+                "<text x=\"0\" y=\"10\" font-family=\"sans-serif\" font-size=\"20\" text-anchor=\"end\" dominant-baseline=\"text-after-edge\" fill=\"rgb(0,0,0)\" fill-opacity=\"1\">&lt;Tom &amp; &quot;Jerry&quot;&gt;</text>\n",
+                "</svg>\n",
+            ),
+        );
+    }
+}
@@ -0,0 +1,417 @@
+///
+/// Week view Widget: a column per day, a row per time slot,
+/// where a range of slots is selected by clicking or dragging
+///
+use iced_graphics::{Backend, Defaults, Primitive, Renderer};
+use iced_native::{
+    layout, mouse, Background, Color, Element, Hasher, Layout, Length,
+    Point, Size, Widget, Event, Clipboard, Rectangle, Font, HorizontalAlignment,
+    VerticalAlignment,
+};
+
+use chrono::prelude::*;
+use chrono::Duration;
+
+use crate::dates::week_start;
+use crate::style::{BACKGROUND_COLOR, BORDER_COLOR, FIRST_TEXT_COLOR, HIGH_LIGHT_COLOR};
+
+/// The week of a date as time slots, sending the selected range
+pub struct WeekView<'a, Message> {
+    state: &'a mut State,
+    date: NaiveDate,
+    on_select: Box<dyn Fn(NaiveDateTime, NaiveDateTime) -> Message>,
+    w: u32,
+    h: u32,
+    size: Option<u16>,
+
+    /// the first hour shown, and the hour the last slot ends
+    hours: (u32, u32),
+
+    /// the length of a slot, in minutes
+    slot: u32,
+}
+
+impl<'a, Message> WeekView<'a, Message> {
+
+    /// create a new [`WeekView`] of the week of the date, from Monday,
+    /// sending the start and the end (exclusive) of the selected slots
+    pub fn new<F>(
+        w: u32,
+        h: u32,
+        state: &'a mut State,
+        date: NaiveDate,
+        on_select: F,
+    ) -> Self
+    where
+        F: 'static + Fn(NaiveDateTime, NaiveDateTime) -> Message,
+    {
+        Self {
+            state,
+            date,
+            on_select: Box::new(on_select),
+            w,
+            h,
+            size: None,
+            hours: (8, 20),
+            slot: 60,
+        }
+    }
+
+    /// Sets the hours shown, from the first one to the end of the last one
+    pub fn hours(mut self, first: u32, end: u32) -> Self {
+        let first = first.min(23);
+        self.hours = (first, end.max(first + 1).min(24));
+        self
+    }
+
+    /// Sets the length of a slot in minutes, an hour by default
+    pub fn slot(mut self, minutes: u32) -> Self {
+        self.slot = match minutes {
+            0..=4 => 5,
+            5..=60 => minutes,
+            _ => 60,
+        };
+        self
+    }
+
+    /// Sets the text size of the [`WeekView`].
+    pub fn size(mut self, size: u16) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// how many slots a day has
+    fn slots(&self) -> u32 {
+        let minutes = (self.hours.1 - self.hours.0) * 60;
+
+        // the last slot may be cut by the end of the hours
+        minutes / self.slot + if minutes % self.slot == 0 { 0 } else { 1 }
+    }
+
+    /// the height of the day names and the width of the hours
+    fn margins(&self) -> (f32, f32) {
+        let size = self.size.unwrap_or(20) as f32;
+        (3.0 * size, 1.5 * size)
+    }
+
+    /// the width of a day and the height of a slot
+    fn cell_size(&self, bounds: Rectangle) -> (f32, f32) {
+        let (left, top) = self.margins();
+        ((bounds.width - left) / 7.0, (bounds.height - top) / self.slots() as f32)
+    }
+
+    /// the start of the slot of the nth day
+    fn slot_start(&self, day: i64, slot: u32) -> NaiveDateTime {
+        let minutes = self.hours.0 * 60 + slot * self.slot;
+        (week_start(self.date) + Duration::days(day)).and_hms(0, 0, 0)
+            + Duration::minutes(minutes as i64)
+    }
+
+    /// the end of the slot starting at the time, at the end of the hours at most
+    fn slot_end(&self, start: NaiveDateTime) -> NaiveDateTime {
+        let end = start.date().and_hms(0, 0, 0) + Duration::hours(self.hours.1 as i64);
+        (start + Duration::minutes(self.slot as i64)).min(end)
+    }
+
+    /// the day and slot under the cursor
+    fn slot_at(&self, bounds: Rectangle, cursor_position: Point) -> Option<(i64, u32)> {
+        let (left, top) = self.margins();
+        let (width, height) = self.cell_size(bounds);
+        let x = cursor_position.x - bounds.x - left;
+        let y = cursor_position.y - bounds.y - top;
+
+        if x < 0.0 || y < 0.0 || !bounds.contains(cursor_position) {
+            return None
+        }
+
+        Some((((x / width) as i64).min(6), ((y / height) as u32).min(self.slots() - 1)))
+    }
+
+    /// the slots from where the drag started to the one under the cursor,
+    /// in the day where it started
+    fn dragged(&self, bounds: Rectangle, cursor_position: Point) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        let (day, from) = self.state.pressed?;
+        let (height, top) = (self.cell_size(bounds).1, self.margins().1);
+
+        // the cursor may leave the view, the slot is the nearest one
+        let y = cursor_position.y - bounds.y - top;
+        let to = (y / height).max(0.0).min(self.slots() as f32 - 1.0) as u32;
+
+        let first = self.slot_start(day, from.min(to));
+        let last = self.slot_start(day, from.max(to));
+        Some((first, self.slot_end(last)))
+    }
+}
+
+impl<'a, Message, B> Widget<Message, Renderer<B>> for WeekView<'a, Message>
+where
+    B: Backend,
+{
+    fn width(&self) -> Length {
+        Length::from(self.w as u16)
+    }
+
+    fn height(&self) -> Length {
+        Length::from(self.h as u16)
+    }
+
+    fn layout(
+        &self,
+        _renderer: &Renderer<B>,
+        _limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(Size::new(self.w as f32, self.h as f32))
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        use std::hash::Hash;
+        struct Marker;
+        std::any::TypeId::of::<Marker>().hash(state);
+
+        self.w.hash(state);
+        self.h.hash(state);
+    }
+
+    /// Draw the names of the days on top, the hours on the left,
+    /// then the slots, the selected or dragged ones highlighted
+    fn draw(
+        &self,
+        _renderer: &mut Renderer<B>,
+        _defaults: &Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+    ) -> (Primitive, mouse::Interaction) {
+        let bounds = layout.bounds();
+        let size = self.size.unwrap_or(20) as f32;
+        let (left, top) = self.margins();
+        let (width, height) = self.cell_size(bounds);
+        let mut v = Vec::new();
+
+        let selection = self
+            .dragged(bounds, cursor_position)
+            .or(self.state.selection);
+
+        for day in 0..7 {
+            let date = week_start(self.date) + Duration::days(day);
+            let x = bounds.x + left + day as f32 * width;
+
+            // Draw the name of the day
+            let b = Rectangle {
+                x,
+                y: bounds.y,
+                width,
+                height: top,
+            };
+
+            v.push(Primitive::Quad {
+                bounds: b,
+                background: Background::Color(if date == self.date {
+                    HIGH_LIGHT_COLOR
+                } else {
+                    BACKGROUND_COLOR
+                }),
+                border_radius: 0,
+                border_width: 1,
+                border_color: BORDER_COLOR,
+            });
+
+            v.push(Primitive::Text {
+                content: date.format("%a %-d").to_string(),
+                bounds: Rectangle { x: b.center_x(), y: b.center_y(), ..b },
+                color: FIRST_TEXT_COLOR,
+                size,
+                font: Font::default(),
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Center,
+            });
+
+            // Draw the slots of the day
+            for slot in 0..self.slots() {
+                let start = self.slot_start(day, slot);
+                let is_selected = match selection {
+                    Some((first, end)) => first <= start && start < end,
+                    None => false,
+                };
+
+                v.push(Primitive::Quad {
+                    bounds: Rectangle {
+                        x,
+                        y: bounds.y + top + slot as f32 * height,
+                        width,
+                        height,
+                    },
+                    background: Background::Color(if is_selected {
+                        HIGH_LIGHT_COLOR
+                    } else {
+                        Color::WHITE
+                    }),
+                    border_radius: 0,
+                    border_width: 1,
+                    border_color: BORDER_COLOR,
+                });
+            }
+        }
+
+        // Draw the time of the slots starting on the hour
+        for slot in 0..self.slots() {
+            let start = self.slot_start(0, slot);
+            if start.minute() != 0 {
+                continue
+            }
+
+            v.push(Primitive::Text {
+                content: start.format("%H:%M").to_string(),
+                bounds: Rectangle {
+                    x: bounds.x + left / 2.0,
+                    y: bounds.y + top + slot as f32 * height,
+                    width: left,
+                    height,
+                },
+                color: FIRST_TEXT_COLOR,
+                size: 0.8 * size,
+                font: Font::default(),
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Top,
+            });
+        }
+
+        (
+            Primitive::Group {
+                primitives: v
+            },
+            if self.slot_at(bounds, cursor_position).is_some() {
+                mouse::Interaction::Pointer
+            } else {
+                mouse::Interaction::default()
+            },
+        )
+    }
+
+    // start a selection when pressed on a slot,
+    // and send it when released
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        messages: &mut Vec<Message>,
+        _renderer: &Renderer<B>,
+        _clipboard: Option<&dyn Clipboard>,
+    ) {
+        let bounds = layout.bounds();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                self.state.pressed = self.slot_at(bounds, cursor_position);
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if let Some((start, end)) = self.dragged(bounds, cursor_position) {
+                    self.state.selection = Some((start, end));
+                    messages.push((self.on_select)(start, end));
+                }
+                self.state.pressed = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a, Message, B> Into<Element<'a, Message, Renderer<B>>> for WeekView<'a, Message>
+where
+    B: Backend,
+    Message: 'a,
+{
+    fn into(self) -> Element<'a, Message, Renderer<B>> {
+        Element::new(self)
+    }
+}
+
+/// The state of a [`WeekView`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct State {
+
+    /// the day and slot where the drag started
+    pressed: Option<(i64, u32)>,
+    selection: Option<(NaiveDateTime, NaiveDateTime)>,
+}
+
+impl State {
+
+    /// Creates a new [`State`], without selection
+    pub fn new() -> State {
+        State::default()
+    }
+
+    /// the selected slots: their start and their end, exclusive
+    pub fn selection(&self) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        self.selection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headless::Headless;
+    use iced_native::{Cache, UserInterface};
+
+    /// Friday 2020-06-05, the week from Monday 1 June
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd(2020, 6, 5)
+    }
+
+    /// the center of the slot of the nth day: with the default size, the hours
+    /// take 60 pixels and the day names 30, a day is 100 wide and a slot 40 high
+    fn slot(day: u32, slot: u32) -> Point {
+        Point::new(60.0 + 100.0 * day as f32 + 50.0, 30.0 + 40.0 * slot as f32 + 20.0)
+    }
+
+    /// feeds the event to a view of the state, with the cursor at the position
+    fn event(state: &mut State, event: mouse::Event, position: Point) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        let mut renderer = Renderer::new(Headless);
+        let view = WeekView::new(760, 510, state, date(), |start, end| (start, end));
+        let mut user_interface = UserInterface::build(view, Size::new(760.0, 510.0), Cache::new(), &mut renderer);
+
+        user_interface.update(vec![Event::Mouse(event)], position, None, &renderer)
+    }
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd(2020, 6, day).and_hms(hour, 0, 0)
+    }
+
+    #[test]
+    fn click_selects_a_slot() {
+        let mut state = State::new();
+
+        assert!(event(&mut state, mouse::Event::ButtonPressed(mouse::Button::Left), slot(0, 1)).is_empty());
+        let messages = event(&mut state, mouse::Event::ButtonReleased(mouse::Button::Left), slot(0, 1));
+
+        assert_eq!(messages, vec![(at(1, 9), at(1, 10))]);
+        assert_eq!(state.selection(), Some((at(1, 9), at(1, 10))));
+    }
+
+    #[test]
+    fn drag_upward_starts_before_it_ends() {
+        let mut state = State::new();
+
+        // from 13:00 up to 10:00 on Wednesday
+        event(&mut state, mouse::Event::ButtonPressed(mouse::Button::Left), slot(2, 5));
+        let to = slot(2, 2);
+        event(&mut state, mouse::Event::CursorMoved { x: to.x, y: to.y }, to);
+        let messages = event(&mut state, mouse::Event::ButtonReleased(mouse::Button::Left), to);
+
+        assert_eq!(messages, vec![(at(3, 10), at(3, 14))]);
+        let (start, end) = state.selection().unwrap();
+        assert!(start < end);
+    }
+
+    #[test]
+    fn drag_stays_in_its_day() {
+        let mut state = State::new();
+
+        // from 9:00 on Monday to above the slots on Tuesday
+        event(&mut state, mouse::Event::ButtonPressed(mouse::Button::Left), slot(0, 1));
+        let messages = event(&mut state, mouse::Event::ButtonReleased(mouse::Button::Left), Point::new(260.0, 10.0));
+
+        assert_eq!(messages, vec![(at(1, 8), at(1, 10))]);
+    }
+}